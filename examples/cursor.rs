@@ -26,11 +26,9 @@ fn test(
     primary_window: Query<&Window, With<PrimaryWindow>>,
 ) {
     if let Ok(window) = primary_window.get_single() {
-        if let Some(cursor) = window.cursor_position() {
-            if let Ok((cam, tcam, t)) = q_cam.get_single() {
-                if let Some(cpos) = tcam.screen_to_world(cursor, cam, t) {
-                    println!("CPOS {}", cpos);
-                }
+        if let Ok((cam, tcam, t)) = q_cam.get_single() {
+            if let Some(cpos) = tcam.cursor_to_world(window, cam, t) {
+                println!("CPOS {}", cpos);
             }
         }
     }
@@ -0,0 +1,74 @@
+/// Scrolls the camera when the cursor is near the edge of the view.
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_tiled_camera::*;
+
+/// How close to an edge (in world units) the cursor must be to scroll.
+const EDGE_SIZE: f32 = 2.0;
+/// Scroll speed in world units per second.
+const SCROLL_SPEED: f32 = 10.0;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            TiledCameraPlugin,
+            DefaultPlugins.set(ImagePlugin::default_nearest()),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, edge_scroll)
+        .run();
+}
+
+fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    commands.spawn(TiledCameraBundle::unit_cam([20, 15]));
+
+    let texture = server.load("8x8.png");
+    for x in -20..20 {
+        for y in -20..20 {
+            if (x + y) % 2 == 0 {
+                continue;
+            }
+            commands.spawn(SpriteBundle {
+                texture: texture.clone(),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::ONE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x as f32, y as f32, 0.0),
+                ..default()
+            });
+        }
+    }
+}
+
+fn edge_scroll(
+    time: Res<Time>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    mut q_cam: Query<(&Camera, &TiledCamera, &GlobalTransform, &mut Transform)>,
+) {
+    let Ok(window) = primary_window.get_single() else {
+        return;
+    };
+    let Ok((cam, tcam, global, mut transform)) = q_cam.get_single_mut() else {
+        return;
+    };
+    let Some(cursor) = tcam.cursor_to_world(window, cam, global) else {
+        return;
+    };
+
+    let [left, right, bottom, top] = tcam.edge_midpoints_world(global);
+    let mut dir = Vec2::ZERO;
+    if cursor.x < left.x + EDGE_SIZE {
+        dir.x -= 1.0;
+    }
+    if cursor.x > right.x - EDGE_SIZE {
+        dir.x += 1.0;
+    }
+    if cursor.y < bottom.y + EDGE_SIZE {
+        dir.y -= 1.0;
+    }
+    if cursor.y > top.y - EDGE_SIZE {
+        dir.y += 1.0;
+    }
+
+    transform.translation += (dir * SCROLL_SPEED * time.delta_seconds()).extend(0.0);
+}
@@ -32,9 +32,6 @@ struct SpriteTextures {
 #[derive(Component)]
 struct GridSprite;
 
-#[derive(Component)]
-struct Cursor;
-
 #[derive(Resource)]
 struct GridEntities(HashMap<IVec2, Entity>);
 
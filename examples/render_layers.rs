@@ -0,0 +1,60 @@
+/// A tiled camera drawing the world on layer 0, with a separate non-tiled
+/// camera drawing an overlay on layer 1 at the window's native resolution.
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_tiled_camera::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            TiledCameraPlugin,
+            DefaultPlugins.set(ImagePlugin::default_nearest()),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    commands.spawn(
+        TiledCameraBundle::unit_cam([12, 12])
+            .with_render_layers(RenderLayers::layer(0))
+            .with_clear_color(Color::rgb_u8(0, 68, 153)),
+    );
+
+    // The overlay camera renders after the tiled camera without clearing,
+    // and isn't affected by the tiled camera's viewport or zoom.
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            ..default()
+        },
+        RenderLayers::layer(1),
+    ));
+
+    // Drawn by the tiled camera, one tile in size.
+    commands.spawn(SpriteBundle {
+        texture: server.load("8x8.png"),
+        sprite: Sprite {
+            custom_size: Some(Vec2::ONE),
+            ..default()
+        },
+        ..default()
+    });
+
+    // Drawn by the overlay camera in window pixels.
+    commands.spawn((
+        SpriteBundle {
+            texture: server.load("8x8.png"),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(64.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(200.0, 150.0, 0.0),
+            ..default()
+        },
+        RenderLayers::layer(1),
+    ));
+}
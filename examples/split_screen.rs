@@ -0,0 +1,62 @@
+/// Two cameras sharing a window with an uneven 70/30 split. Each camera
+/// scales and letterboxes it's view independently within it's region.
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_tiled_camera::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            TiledCameraPlugin,
+            DefaultPlugins.set(ImagePlugin::default_nearest()),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, print_cursor)
+        .run();
+}
+
+fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    commands.spawn(
+        TiledCameraBundle::unit_cam([12, 12])
+            .with_window_region(Rect::new(0.0, 0.0, 0.7, 1.0))
+            .with_clear_color(Color::rgb_u8(0, 68, 153)),
+    );
+
+    // Each camera needs a unique order to render to the same window.
+    commands
+        .spawn(
+            TiledCameraBundle::unit_cam([6, 6])
+                .with_window_region(Rect::new(0.7, 0.0, 1.0, 1.0))
+                .with_clear_color(Color::rgb_u8(68, 0, 153)),
+        )
+        .add(|id, world: &mut World| {
+            world.get_mut::<Camera>(id).unwrap().order = 1;
+        });
+
+    commands.spawn(SpriteBundle {
+        texture: server.load("8x8.png"),
+        sprite: Sprite {
+            custom_size: Some(Vec2::ONE),
+            ..default()
+        },
+        ..default()
+    });
+}
+
+fn print_cursor(
+    mut cursor_moved: EventReader<CursorMoved>,
+    q_cam: Query<(&Camera, &TiledCamera, &GlobalTransform)>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Ok(window) = q_window.get_single() else {
+        return;
+    };
+    // Cursor positions are logical and counted from the top left of the
+    // window, `cursor_event_to_world` converts them to screen space.
+    for event in cursor_moved.read() {
+        for (cam, tcam, t) in q_cam.iter() {
+            if let Some(world) = tcam.cursor_event_to_world(event, window, cam, t) {
+                println!("Camera {} cursor {}", cam.order, world);
+            }
+        }
+    }
+}
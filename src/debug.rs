@@ -0,0 +1,195 @@
+//! Debug visualizations for [`TiledCamera`]s, drawn with bevy's [`Gizmos`].
+//!
+//! Requires the `debug` feature.
+use bevy::{
+    gizmos::gizmos::Gizmos,
+    math::{Rect, Vec2},
+    prelude::{
+        App, Camera, Color, Component, Entity, GlobalTransform, IntoSystemConfigs, Plugin,
+        PostUpdate, Query, Res, Resource, With,
+    },
+    render::camera::RenderTarget,
+    transform::TransformSystem,
+    window::{PrimaryWindow, Window},
+};
+
+use crate::{cursor_to_screen, TiledCamera};
+
+/// Plugin that draws debug visualizations for every [`TiledCamera`].
+///
+/// See [`SafeZoneGuides`] and [`DebugGrid`].
+pub struct TiledCameraDebugPlugin;
+
+impl Plugin for TiledCameraDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SafeZoneGuides>().add_systems(
+            PostUpdate,
+            (draw_safe_zones, draw_debug_grid).after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Add to a [`TiledCamera`] entity to draw it's virtual tile grid, along
+/// with the tile under the cursor.
+///
+/// Lines are drawn in world space so they match the camera's [`WorldSpace`](crate::WorldSpace).
+/// Requires [`TiledCameraDebugPlugin`].
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct DebugGrid {
+    /// The color of the grid lines.
+    pub color: Color,
+    /// The color used to highlight the tile under the cursor.
+    pub cursor_color: Color,
+    /// Which lines of the grid are drawn.
+    pub mode: DebugGridMode,
+}
+
+impl Default for DebugGrid {
+    fn default() -> Self {
+        Self {
+            color: Color::rgba(1.0, 1.0, 1.0, 0.25),
+            cursor_color: Color::YELLOW,
+            mode: DebugGridMode::Cells,
+        }
+    }
+}
+
+impl DebugGrid {
+    /// A [`DebugGrid`] that only draws the bounds of the grid.
+    pub fn bounds() -> Self {
+        Self {
+            mode: DebugGridMode::Bounds,
+            ..Default::default()
+        }
+    }
+
+    /// Set the color of the grid lines.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The world space line segments drawn for a camera's grid.
+    pub(crate) fn lines(
+        &self,
+        tiled_cam: &TiledCamera,
+        transform: &GlobalTransform,
+    ) -> Vec<(Vec2, Vec2)> {
+        match self.mode {
+            DebugGridMode::Bounds => {
+                let Rect { min, max } = tiled_cam.grid_world_rect(transform);
+                let (top_left, bottom_right) = (Vec2::new(min.x, max.y), Vec2::new(max.x, min.y));
+                vec![
+                    (min, bottom_right),
+                    (bottom_right, max),
+                    (max, top_left),
+                    (top_left, min),
+                ]
+            }
+            DebugGridMode::Cells => tiled_cam.grid_lines_world(transform).collect(),
+        }
+    }
+}
+
+/// Which lines a [`DebugGrid`] draws.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DebugGridMode {
+    /// Only draw the outer bounds of the grid.
+    Bounds,
+    /// Draw the outline of every tile in the grid.
+    #[default]
+    Cells,
+}
+
+/// Configures the "safe zone" guides drawn by [`TiledCameraDebugPlugin`].
+///
+/// For each aspect ratio the area the camera would show at that window aspect
+/// is drawn, along with the area that's visible for all of them. Keeping
+/// important content inside the safe zone ensures it's visible at any of the
+/// given aspects.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SafeZoneGuides {
+    /// Whether the guides are drawn.
+    pub enabled: bool,
+    /// The window aspect ratios (width / height) to show.
+    pub aspects: Vec<f32>,
+    /// The color of the per-aspect rects.
+    pub color: Color,
+    /// The color of the safe zone.
+    pub safe_color: Color,
+}
+
+impl Default for SafeZoneGuides {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            aspects: vec![16.0 / 9.0, 4.0 / 3.0, 21.0 / 9.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+            safe_color: Color::GREEN,
+        }
+    }
+}
+
+fn draw_safe_zones(
+    guides: Res<SafeZoneGuides>,
+    q_cam: Query<(&TiledCamera, &GlobalTransform)>,
+    mut gizmos: Gizmos,
+) {
+    if !guides.enabled {
+        return;
+    }
+    for (tiled_cam, transform) in q_cam.iter() {
+        if !tiled_cam.is_initialized() {
+            continue;
+        }
+        for rect in tiled_cam.aspect_view_rects(transform, &guides.aspects) {
+            gizmos.rect_2d(rect.center(), 0.0, rect.size(), guides.color);
+        }
+        let safe = tiled_cam.safe_zone(transform, &guides.aspects);
+        if !safe.is_empty() {
+            gizmos.rect_2d(
+                safe.center(),
+                0.0,
+                safe.size().max(Vec2::ZERO),
+                guides.safe_color,
+            );
+        }
+    }
+}
+
+fn draw_debug_grid(
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    q_cam: Query<(&TiledCamera, &Camera, &GlobalTransform, &DebugGrid)>,
+    mut gizmos: Gizmos,
+) {
+    let primary_window = primary_window.get_single().ok();
+    for (tiled_cam, cam, transform, grid) in q_cam.iter() {
+        if !tiled_cam.is_initialized() {
+            continue;
+        }
+        for (start, end) in grid.lines(tiled_cam, transform) {
+            gizmos.line_2d(start, end, grid.color);
+        }
+
+        let RenderTarget::Window(window_ref) = tiled_cam.output(cam).0 else {
+            continue;
+        };
+        let Some(window) = window_ref
+            .normalize(primary_window)
+            .and_then(|w| windows.get(w.entity()).ok())
+        else {
+            continue;
+        };
+        let Some(cursor) = window.cursor_position() else {
+            continue;
+        };
+        let screen_pos = cursor_to_screen(cursor, window);
+        if let Some(pick) = tiled_cam.pick(screen_pos, cam, transform) {
+            if pick.in_bounds {
+                let cell = tiled_cam.cell_size_world();
+                gizmos.rect_2d(pick.tile_center, 0.0, cell, grid.cursor_color);
+            }
+        }
+    }
+}
@@ -34,6 +34,16 @@
 //! You can choose between [WorldSpace::Units] or [WorldSpace::Pixels].
 //! The camera supports either, and it's up to you to decide which you prefer.
 //!
+//! # Screen Space
+//! Screen positions, as used by [TiledCamera::screen_to_world],
+//! [TiledCamera::world_to_screen] and [TiledCamera::screen_in_viewport], are
+//! in physical pixels with the origin at the bottom left of the window.
+//!
+//! Viewport positions like [TiledCamera::viewport_pos] follow bevy's
+//! [Viewport](bevy::render::camera::Viewport) and are counted from the top left
+//! of the window instead. Cursor positions are in logical pixels counted from
+//! the top left, use [TiledCamera::cursor_event_to_world] to convert them.
+//!
 //! ## Versions
 //! | bevy | bevy_tiled_camera |
 //! | --- | --- |
@@ -70,13 +80,26 @@
 //! ```
 use bevy::{
     ecs::prelude::*,
-    math::{IVec2, Mat4, UVec2, Vec2, Vec3},
+    hierarchy::Parent,
+    log::warn,
+    math::{IVec2, Mat4, Rect, UVec2, Vec2, Vec3},
     prelude::{
-        default, App, Camera, Camera2dBundle, Color, GlobalTransform, OrthographicProjection,
-        Plugin, Update,
+        default, App, Assets, Camera, Camera2dBundle, Color, GlobalTransform, Image,
+        IntoSystemConfigs, OrthographicProjection, Plugin, PostUpdate, ReflectComponent,
+        ReflectDefault, Transform,
     },
-    render::camera::{ClearColorConfig, ScalingMode, Viewport},
-    window::{PrimaryWindow, Window, WindowResized},
+    reflect::Reflect,
+    render::{
+        camera::{CameraUpdateSystem, ClearColorConfig, RenderTarget, ScalingMode, Viewport},
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        texture::{BevyDefault, ImageSampler, TextureFormatPixelInfo},
+        view::{Layer, RenderLayers, ViewTarget, Visibility},
+    },
+    sprite::{Anchor, Sprite, SpriteBundle},
+    time::Time,
+    transform::TransformSystem,
+    window::{CursorMoved, PrimaryWindow, Window, WindowRef, WindowResized},
 };
 use sark_grids::{
     point::{Point2d, Size2d},
@@ -86,11 +109,73 @@ use sark_grids::{
 
 pub use sark_grids::world_grid::WorldSpace;
 
+#[cfg(feature = "debug")]
+mod debug;
+#[cfg(feature = "debug")]
+pub use debug::*;
+
+/// The render layer used to draw [`TiledCamera::letterbox_color`].
+pub const LETTERBOX_LAYER: Layer = RenderLayers::TOTAL_LAYERS as Layer - 1;
+
+/// The render layer used to upscale cameras with a [`TiledCamera::render_scale`]
+/// below 1 to their viewport.
+pub const RENDER_SCALE_LAYER: Layer = RenderLayers::TOTAL_LAYERS as Layer - 2;
+
+/// The camera order a camera with a [`TiledCamera::render_scale`] below 1 is
+/// moved to while it renders to an image, offset by the camera's own order.
+///
+/// This makes sure the image is rendered before any camera draws to the
+/// window, including the camera that upscales the image, which takes over
+/// the tiled camera's original order.
+pub const RENDER_SCALE_ORDER: isize = -(1 << 16);
+
+/// The camera order the letterbox bars for [`TiledCamera::letterbox_color`]
+/// are drawn at, offset by the tiled camera's own order.
+///
+/// This keeps letterbox cameras after, and never sharing an order with, any
+/// regular camera. Cameras that should draw over the bars (IE: UI) need an
+/// order higher than this.
+pub const LETTERBOX_ORDER: isize = 1 << 16;
+
+/// Plugin that manages the viewports of all [`TiledCamera`]s.
+///
+/// Viewports are recomputed in [`PostUpdate`], after any changes made to the
+/// camera during [`Update`](bevy::app::Update). A camera's viewport is recomputed at most once
+/// per frame, using the final state of the camera, no matter how many times
+/// it was changed.
 pub struct TiledCameraPlugin;
 
 impl Plugin for TiledCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (on_window_resized, on_camera_changed));
+        app.register_type::<TiledCamera>()
+            .register_type::<CameraWorldSpace>()
+            .register_type::<ResponsiveMode>()
+            .register_type::<CenterBias>()
+            .register_type::<ScaleAxis>()
+            .register_type::<MinZoomPolicy>()
+            .register_type::<HiDpiMode>()
+            .register_type::<CameraOrigin>()
+            .register_type::<SnapGranularity>()
+            .register_type::<ViewportMargins>()
+            .add_event::<TiledCameraInitialized>()
+            .add_event::<TiledCameraResized>()
+            .add_event::<ZoomChanged>()
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        update_viewports,
+                        update_render_scale,
+                        update_letterbox,
+                        follow_target,
+                        clamp_to_bounds,
+                    )
+                        .chain()
+                        .before(CameraUpdateSystem)
+                        .before(TransformSystem::TransformPropagate),
+                    update_render_transform.after(TransformSystem::TransformPropagate),
+                ),
+            );
     }
 }
 
@@ -112,6 +197,7 @@ impl Plugin for TiledCameraPlugin {
 pub struct TiledCameraBundle {
     cam2d_bundle: Camera2dBundle,
     tiled_camera: TiledCamera,
+    render_layers: RenderLayers,
 }
 
 impl TiledCameraBundle {
@@ -120,6 +206,32 @@ impl TiledCameraBundle {
         Self {
             cam2d_bundle: Camera2dBundle { ..default() },
             tiled_camera: default(),
+            render_layers: default(),
+        }
+    }
+
+    /// Construct a camera from an existing [`Camera2dBundle`], IE: to set the
+    /// camera's render order or HDR.
+    ///
+    /// The bundle's viewport and projection scaling will be managed by the
+    /// [`TiledCamera`], other builder methods can be used as normal.
+    pub fn from_camera_bundle(cam2d_bundle: Camera2dBundle) -> Self {
+        Self {
+            cam2d_bundle,
+            tiled_camera: default(),
+            render_layers: default(),
+        }
+    }
+
+    /// Construct a camera from a [`TiledCameraConfig`].
+    pub fn from_config(config: &TiledCameraConfig) -> Self {
+        let bundle = Self::new()
+            .with_world_space(config.world_space)
+            .with_pixels_per_tile(config.pixels_per_tile)
+            .with_tile_count(config.tile_count);
+        match config.clear_color {
+            Some(color) => bundle.with_clear_color(color),
+            None => bundle,
         }
     }
 
@@ -137,6 +249,15 @@ impl TiledCameraBundle {
             .with_tile_count(tile_count)
     }
 
+    /// Set the [`RenderLayers`] the camera draws. Defaults to layer 0.
+    ///
+    /// This can be used to draw the world with a tiled camera and UI or other
+    /// overlays with a separate, non-tiled camera on another layer.
+    pub fn with_render_layers(mut self, layers: RenderLayers) -> Self {
+        self.render_layers = layers;
+        self
+    }
+
     /// Set the camera's [`WorldSpace`].
     pub fn with_world_space(mut self, world_space: WorldSpace) -> Self {
         self.tiled_camera.set_world_space(world_space);
@@ -149,6 +270,14 @@ impl TiledCameraBundle {
         self
     }
 
+    /// Fill the letterbox bars around the camera's viewport with `color`.
+    ///
+    /// The camera's clear color will only be visible inside the viewport.
+    pub fn with_letterbox_color(mut self, color: Color) -> Self {
+        self.tiled_camera.letterbox_color = Some(color);
+        self
+    }
+
     /// Set the camera's pixels per tile.
     ///
     /// This along with tile count and [`WorldSpace`] define how the camera
@@ -169,6 +298,178 @@ impl TiledCameraBundle {
         self
     }
 
+    /// Render at exactly `resolution`, regardless of the camera's tile count.
+    ///
+    /// See [`TiledCamera::target_resolution_override`].
+    pub fn with_target_resolution(mut self, resolution: impl Size2d) -> Self {
+        self.tiled_camera.target_resolution_override = Some(resolution.as_uvec2());
+        self
+    }
+
+    /// Set the resolution UI is designed against. Defaults to the camera's
+    /// target resolution.
+    ///
+    /// See [`TiledCamera::design_scale`].
+    pub fn with_design_resolution(mut self, resolution: impl Size2d) -> Self {
+        self.tiled_camera.design_resolution = Some(resolution.as_uvec2());
+        self
+    }
+
+    /// Set the camera's [`ResponsiveMode`].
+    ///
+    /// This determines how the camera fills any window space left over after
+    /// scaling up the target resolution.
+    pub fn with_responsive_mode(mut self, mode: ResponsiveMode) -> Self {
+        self.tiled_camera.responsive_mode = mode;
+        self
+    }
+
+    /// Set the camera's [`CenterBias`].
+    ///
+    /// This determines which side of the viewport receives the extra pixel
+    /// of letterboxing when it can't be split evenly.
+    pub fn with_center_bias(mut self, bias: CenterBias) -> Self {
+        self.tiled_camera.center_bias = bias;
+        self
+    }
+
+    /// Set where the camera's translation appears in the viewport, from
+    /// `[0,0]` at the bottom left to `[1,1]` at the top right.
+    pub fn with_camera_anchor(mut self, anchor: Vec2) -> Self {
+        self.tiled_camera.camera_anchor = anchor;
+        self
+    }
+
+    /// Set the window the camera renders to. Defaults to the primary window.
+    ///
+    /// The camera's viewport will be sized to fit this window.
+    pub fn with_window(mut self, window: Entity) -> Self {
+        self.cam2d_bundle.camera.target = RenderTarget::Window(WindowRef::Entity(window));
+        self
+    }
+
+    /// Set the camera's [`ScaleAxis`].
+    ///
+    /// This determines which axis of the target resolution is guaranteed to
+    /// be visible.
+    pub fn with_scale_axis(mut self, axis: ScaleAxis) -> Self {
+        self.tiled_camera.scale_axis = axis;
+        self
+    }
+
+    /// Set whether the camera's zoom is restricted to whole numbers.
+    ///
+    /// See [`TiledCamera::integer_scaling`].
+    pub fn with_integer_scaling(mut self, integer_scaling: bool) -> Self {
+        self.tiled_camera.integer_scaling = integer_scaling;
+        self
+    }
+
+    /// Set the smallest zoom the camera will use, regardless of window size.
+    ///
+    /// See [`MinZoomPolicy`] for what happens if the window is too small.
+    pub fn with_min_zoom(mut self, min_zoom: u32) -> Self {
+        self.tiled_camera.min_zoom = min_zoom;
+        self
+    }
+
+    /// Set the camera's [`CameraOrigin`], which determines the tile at index
+    /// `[0,0]`.
+    pub fn with_origin(mut self, origin: CameraOrigin) -> Self {
+        self.tiled_camera.origin = origin;
+        self
+    }
+
+    /// Set the camera's [`MinZoomPolicy`].
+    ///
+    /// This determines what happens when the window is too small to fit the
+    /// target resolution at the camera's minimum zoom.
+    pub fn with_min_zoom_policy(mut self, policy: MinZoomPolicy) -> Self {
+        self.tiled_camera.min_zoom_policy = policy;
+        self
+    }
+
+    /// Set the camera's [`HiDpiMode`].
+    pub fn with_hidpi_mode(mut self, mode: HiDpiMode) -> Self {
+        self.tiled_camera.hidpi_mode = mode;
+        self
+    }
+
+    /// Set the region of the window the camera renders to.
+    ///
+    /// The region is in normalized window coordinates, where `[0,0]` is the
+    /// top left of the window and `[1,1]` is the bottom right. The camera's
+    /// viewport will be scaled and letterboxed within this region.
+    pub fn with_window_region(mut self, region: Rect) -> Self {
+        self.tiled_camera.window_region = region;
+        self
+    }
+
+    /// Set the space in physical pixels to keep clear around the edges of the
+    /// camera's window region.
+    ///
+    /// See [`TiledCamera::viewport_margins`].
+    pub fn with_viewport_margins(mut self, margins: ViewportMargins) -> Self {
+        self.tiled_camera.viewport_margins = margins;
+        self
+    }
+
+    /// If set, the camera will stay inactive until it's window exists and it's
+    /// viewport has been set up for the first time.
+    ///
+    /// This avoids rendering with an invalid viewport in apps that create
+    /// their windows after spawning the camera.
+    pub fn with_defer_until_window(mut self, defer: bool) -> Self {
+        self.tiled_camera.defer_until_window = defer;
+        self.cam2d_bundle.camera.is_active = !defer;
+        self
+    }
+
+    /// Compute the camera's viewport once when it's first set up, then ignore
+    /// any further window resizes.
+    ///
+    /// Changes to the camera's settings will still update the viewport, using
+    /// the window size from the initial setup.
+    pub fn with_fixed_viewport_after_init(mut self) -> Self {
+        self.tiled_camera.fixed_viewport = true;
+        self
+    }
+
+    /// Set the camera's metatile size, in tiles.
+    pub fn with_metatile_size(mut self, metatile_size: impl Size2d) -> Self {
+        self.tiled_camera.metatile_size = metatile_size.as_uvec2();
+        self
+    }
+
+    /// Set the world space bounds the camera view is kept inside of.
+    pub fn with_bounds(mut self, bounds: Rect) -> Self {
+        self.tiled_camera.bounds = Some(bounds);
+        self
+    }
+
+    /// Set the near and far planes of the camera's projection.
+    ///
+    /// Sprites are visible if their z position is between `camera_z - far`
+    /// and `camera_z - near`. By default the camera is at `z = 0` with `near`
+    /// at -1000 and `far` at 1000, so sprites can use z values from -1000 to
+    /// 1000.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `near` is not less than `far`.
+    pub fn with_depth_range(mut self, near: f32, far: f32) -> Self {
+        assert!(
+            near < far,
+            "Invalid depth range: near ({}) must be less than far ({})",
+            near,
+            far
+        );
+        self.cam2d_bundle.projection.near = near;
+        self.cam2d_bundle.projection.far = far;
+        self.tiled_camera.depth_range = (near, far);
+        self
+    }
+
     /// Set the initial world position for the camera.
     pub fn with_camera_position(mut self, world_pos: impl Point2d) -> Self {
         let pos = &mut self.cam2d_bundle.transform.translation;
@@ -177,27 +478,252 @@ impl TiledCameraBundle {
     }
 }
 
+/// Plain camera settings that can be loaded from a file (IE: a RON scene
+/// definition) and turned into a camera with [`TiledCameraBundle::from_config`].
+///
+/// Requires the `serde` feature to be serialized.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TiledCameraConfig {
+    /// How many tiles the camera shows.
+    pub tile_count: UVec2,
+    /// How many pixels make up a single tile.
+    pub pixels_per_tile: UVec2,
+    /// The camera's [`WorldSpace`].
+    #[cfg_attr(feature = "serde", serde(with = "WorldSpaceDef"))]
+    pub world_space: WorldSpace,
+    /// The camera's clear color. If [`None`] the app's default clear color
+    /// is used.
+    pub clear_color: Option<Color>,
+}
+
+impl Default for TiledCameraConfig {
+    fn default() -> Self {
+        let cam = TiledCamera::default();
+        Self {
+            tile_count: cam.tile_count,
+            pixels_per_tile: cam.pixels_per_tile,
+            world_space: cam.world_space(),
+            clear_color: None,
+        }
+    }
+}
+
+/// A reflectable mirror of [`WorldSpace`], which is defined in `sark_grids`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+enum CameraWorldSpace {
+    #[default]
+    Units,
+    Pixels,
+}
+
+impl From<WorldSpace> for CameraWorldSpace {
+    fn from(world_space: WorldSpace) -> Self {
+        match world_space {
+            WorldSpace::Units => Self::Units,
+            WorldSpace::Pixels => Self::Pixels,
+        }
+    }
+}
+
+impl From<CameraWorldSpace> for WorldSpace {
+    fn from(world_space: CameraWorldSpace) -> Self {
+        match world_space {
+            CameraWorldSpace::Units => Self::Units,
+            CameraWorldSpace::Pixels => Self::Pixels,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "WorldSpace")]
+enum WorldSpaceDef {
+    Units,
+    Pixels,
+}
+
 /// A camera with a virtual grid for displaying low resolution pixel art.
 ///
 /// Contains various functions for translating points between world space and
 /// the camera's virtual grid tiles.
-#[derive(Component)]
+///
+/// Camera rotation isn't supported. The camera's grid, bounds and world/tile
+/// conversions all assume an axis aligned camera, and in debug builds they
+/// will panic if the camera's transform is rotated. Only
+/// [`TiledCamera::screen_to_world`] and [`TiledCamera::world_to_screen`] use
+/// the camera's full transform.
+///
+/// The camera's runtime state isn't reflected. It's [`WorldSpace`] is
+/// reflected as the `world_space` field, and is applied to the camera's grid
+/// on the next viewport update.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
 pub struct TiledCamera {
     /// Pixels per tile determines the size of your tiles/art, depending on
     /// the camera's [`WorldSpace`].
     pub pixels_per_tile: UVec2,
     /// The number of virtual grid tiles in the camera's viewport.
+    ///
+    /// Depending on the camera's [`ResponsiveMode`] more tiles than this may
+    /// be visible.
     pub tile_count: UVec2,
+    /// The size of a metatile, in tiles. Metatiles group tiles together into
+    /// a coarser grid, with metatile `[0,0]` starting at tile `[0,0]`.
+    pub metatile_size: UVec2,
+    /// How the camera fills window space left over after scaling.
+    pub responsive_mode: ResponsiveMode,
+    /// Which side of the viewport receives the extra pixel of letterboxing
+    /// when it can't be split evenly.
+    pub center_bias: CenterBias,
+    /// Where the camera's translation appears in the viewport, from `[0,0]`
+    /// at the bottom left to `[1,1]` at the top right. Defaults to the center.
+    ///
+    /// The camera's virtual grid and camera-local positions are always
+    /// relative to the center of the view, tile indices are relative to the
+    /// camera's [`CameraOrigin`].
+    pub camera_anchor: Vec2,
+    /// Which tile of the camera's virtual grid is at index `[0,0]`. Defaults
+    /// to [`CameraOrigin::Center`].
+    pub origin: CameraOrigin,
+    /// Determines which axis of the target resolution is guaranteed to be
+    /// visible when choosing the camera's zoom.
+    pub scale_axis: ScaleAxis,
+    /// The region of the window the camera renders to, in normalized window
+    /// coordinates where `[0,0]` is the top left of the window.
+    ///
+    /// This can be used for split screen - each camera will scale and
+    /// letterbox it's viewport independently inside it's region.
+    pub window_region: Rect,
+    /// Space in physical pixels to keep clear around the edges of the camera's
+    /// window region, IE: for a HUD.
+    ///
+    /// The viewport is scaled and letterboxed inside the remaining area.
+    pub viewport_margins: ViewportMargins,
+    /// Optional world space bounds. If set the camera will be moved so it's
+    /// view stays inside the bounds.
+    pub bounds: Option<Rect>,
+    /// If set, the camera renders at exactly this resolution instead of
+    /// `pixels_per_tile * tile_count`.
+    ///
+    /// The override wins over `tile_count`, which is ignored while it's set.
+    /// The camera shows `resolution / pixels_per_tile` tiles, which may
+    /// include a partial tile at each edge of the view. `pixels_per_tile` is
+    /// still used for the size of a tile.
+    pub target_resolution_override: Option<UVec2>,
+    /// The resolution UI is designed against.
+    ///
+    /// If [`None`] (the default) this is set to the camera's target
+    /// resolution on it's first viewport update. See
+    /// [`TiledCamera::design_scale`].
+    pub design_resolution: Option<UVec2>,
+    /// If set, the letterbox bars around the camera's viewport are filled with
+    /// this color instead of the camera's clear color.
+    ///
+    /// The bars are drawn as sprites by a second camera, on render layer
+    /// [`LETTERBOX_LAYER`], with an `order` of [`LETTERBOX_ORDER`] plus the
+    /// tiled camera's order.
+    pub letterbox_color: Option<Color>,
+    /// The resolution the camera renders at relative to it's viewport, from
+    /// 0 to 1. Defaults to 1.
+    ///
+    /// Below 1 the camera renders to an image of
+    /// [`TiledCamera::render_image_size`], which a second camera on render
+    /// layer [`RENDER_SCALE_LAYER`] upscales to the viewport. This trades
+    /// sharpness for performance on weak GPUs. The camera's grid and all
+    /// conversions are unaffected.
+    ///
+    /// While the image is in use the plugin manages the camera's `target` and
+    /// `order` (see [`RENDER_SCALE_ORDER`]), they're restored once the scale
+    /// goes back to 1. Only cameras rendering to a window are upscaled.
+    pub render_scale: f32,
+    /// The step size used when snapping positions to the pixel grid.
+    ///
+    /// See [`TiledCamera::snap_to_pixel`] and [`PixelSnap`].
+    pub snap_granularity: SnapGranularity,
+    /// The smallest zoom the camera will use, regardless of window size.
+    pub min_zoom: u32,
+    /// If set (the default) the camera's zoom is always a whole number so
+    /// every game pixel is the same size on screen.
+    ///
+    /// If disabled the zoom is chosen so the viewport exactly fills the window
+    /// along the camera's [`ScaleAxis`], at the cost of some blurring.
+    pub integer_scaling: bool,
+    /// Determines what happens when the window is too small to fit the
+    /// target resolution at [`TiledCamera::min_zoom`].
+    pub min_zoom_policy: MinZoomPolicy,
+    /// Whether the camera's zoom is chosen in physical or logical pixels on
+    /// HiDPI displays. Defaults to [`HiDpiMode::Physical`].
+    pub hidpi_mode: HiDpiMode,
+    /// A world space offset applied only when rendering, IE: for screen shake.
+    ///
+    /// The offset is applied to the camera's [`GlobalTransform`] after
+    /// transform propagation, the camera's [`Transform`] is left unchanged.
+    pub render_offset: Vec2,
+    /// If set, the rendered camera position is rounded to whole world units
+    /// when using [`WorldSpace::Units`], IE: the camera will always be
+    /// centered on a tile boundary.
+    ///
+    /// Like [`TiledCamera::render_offset`] this is only applied to the
+    /// camera's [`GlobalTransform`], so sub-unit movement accumulates in the
+    /// camera's [`Transform`]. It has no effect in [`WorldSpace::Pixels`].
+    pub integer_unit_position: bool,
+    /// If set, debug builds will check that screen/world conversions round
+    /// trip to within a pixel and log a warning if they don't.
+    ///
+    /// This has no effect in release builds.
+    pub verify_conversions: bool,
+    /// The camera's [`WorldSpace`], reflected so it can be edited in an
+    /// inspector. Kept in sync with the grid's world space.
+    world_space: CameraWorldSpace,
+    /// Overrides the zoom computed from the window size.
+    #[reflect(ignore)]
+    forced_zoom: Option<u32>,
+    /// If set the plugin will stop managing the camera's viewport.
+    #[reflect(ignore)]
+    suspended: bool,
+    /// If set window resizes are ignored after the first viewport update.
+    #[reflect(ignore)]
+    fixed_viewport: bool,
+    /// If set the camera will be activated after it's first viewport update.
+    #[reflect(ignore)]
+    defer_until_window: bool,
+    /// The near and far planes set with [`TiledCameraBundle::with_depth_range`].
+    #[reflect(ignore)]
+    depth_range: (f32, f32),
+    /// The window target and order the camera had before it started
+    /// rendering to an image for [`TiledCamera::render_scale`].
+    #[reflect(ignore)]
+    scaled_output: Option<(RenderTarget, isize)>,
+    /// Whether a warning has been logged for the camera's current invalid
+    /// settings. Cleared once the settings are valid again.
+    #[reflect(ignore)]
+    warned_invalid: bool,
+    /// Whether the viewport has been set up at least once.
+    #[reflect(ignore)]
+    initialized: bool,
+    /// Camera position before any render offset was applied.
+    #[reflect(ignore)]
+    logical_pos: Vec2,
     /// World grid used for transforming positions.
+    #[reflect(ignore)]
     grid: WorldGrid,
     /// Camera zoom from the last viewport update.
-    zoom: u32,
+    #[reflect(ignore)]
+    zoom: f32,
     /// Viewport size from the last viewport update.
+    #[reflect(ignore)]
     vp_size: UVec2,
     /// Viewport position from the last viewport update.
+    #[reflect(ignore)]
     vp_pos: UVec2,
     /// Window resolution from the last viewport update.
+    #[reflect(ignore)]
     win_size: UVec2,
+    /// Scale factor of the window from the last viewport update.
+    #[reflect(ignore)]
+    scale_factor: f32,
+    #[reflect(ignore)]
     ortho_size: f32,
 }
 
@@ -209,7 +735,9 @@ impl TiledCamera {
         Self {
             pixels_per_tile,
             tile_count,
+            design_resolution: None,
             grid: WorldGrid::unit_grid(tile_count, pixels_per_tile),
+            world_space: CameraWorldSpace::Units,
             ..default()
         }
     }
@@ -221,356 +749,5830 @@ impl TiledCamera {
         Self {
             pixels_per_tile,
             tile_count,
+            design_resolution: None,
             grid: WorldGrid::pixel_grid(tile_count, pixels_per_tile),
+            world_space: CameraWorldSpace::Pixels,
             ..default()
         }
     }
 
-    /// Retrieve the target resolution (in pixels) of the camera.
-    pub fn target_resolution(&self) -> UVec2 {
-        self.pixels_per_tile * self.tile_count
-    }
+    /// Suggest a `(pixels_per_tile, tile_count)` configuration that will make
+    /// tiles appear close to `desired_tile_screen_px` physical pixels in size
+    /// on a window of size `window_res`, with integer zoom.
+    ///
+    /// The suggested tile count will fill as much of the window as possible,
+    /// but will never be larger than `world_tiles`.
+    pub fn suggest_config(
+        window_res: UVec2,
+        desired_tile_screen_px: UVec2,
+        world_tiles: UVec2,
+    ) -> (UVec2, UVec2) {
+        let desired = desired_tile_screen_px.max(UVec2::ONE);
+        let tile_count = (window_res / desired).clamp(UVec2::ONE, world_tiles.max(UVec2::ONE));
 
-    // Viewport size from the last viewport update
-    pub fn viewport_size(&self) -> UVec2 {
-        self.vp_size
+        let mut best = (desired, u32::MAX);
+        for target_zoom in 1..=desired.max_element() {
+            let ppt = (desired.as_vec2() / target_zoom as f32)
+                .round()
+                .as_uvec2()
+                .max(UVec2::ONE);
+            let zoom = (window_res / (ppt * tile_count)).min_element().max(1);
+            let screen_size = (ppt * zoom).as_ivec2();
+            let error = (screen_size - desired.as_ivec2()).abs();
+            let error = (error.x + error.y) as u32;
+            // Prefer the higher resolution config when equally close.
+            if error < best.1 {
+                best = (ppt, error);
+            }
+        }
+        (best.0, tile_count)
     }
 
-    // Viewport position from the last viewport update
-    pub fn viewport_pos(&self) -> UVec2 {
-        self.vp_pos
+    /// Reset the camera's settings to their defaults.
+    ///
+    /// Unlike replacing the camera with [`TiledCamera::default`], the camera's
+    /// `window_region`, `viewport_margins` and the state from the last
+    /// viewport update are kept, so the camera stays attached to it's part of
+    /// the window. Settings that can only be set through the
+    /// [`TiledCameraBundle`] builder, like
+    /// [`TiledCameraBundle::with_fixed_viewport_after_init`], are kept as well.
+    pub fn reset(&mut self) {
+        *self = Self {
+            window_region: self.window_region,
+            viewport_margins: self.viewport_margins,
+            fixed_viewport: self.fixed_viewport,
+            defer_until_window: self.defer_until_window,
+            depth_range: self.depth_range,
+            scaled_output: self.scaled_output.clone(),
+            initialized: self.initialized,
+            logical_pos: self.logical_pos,
+            zoom: self.zoom,
+            vp_size: self.vp_size,
+            vp_pos: self.vp_pos,
+            win_size: self.win_size,
+            scale_factor: self.scale_factor,
+            ortho_size: self.ortho_size,
+            ..default()
+        };
     }
 
-    /// Window resolution from the last viewport update
-    pub fn window_resolution(&self) -> UVec2 {
-        self.win_size
+    /// Center the camera on `world_point` and set it to show exactly
+    /// `tile_count` tiles.
+    ///
+    /// Afterwards `world_point` will be in the camera's center tile, at index
+    /// `[0,0]` with [`CameraOrigin::Center`].
+    pub fn frame_point_with_tiles(
+        &mut self,
+        world_point: Vec2,
+        tile_count: UVec2,
+        transform: &mut Transform,
+    ) {
+        self.tile_count = tile_count;
+        self.grid.tile_count = tile_count;
+        let pos = world_point - self.anchor_offset();
+        transform.translation = pos.extend(transform.translation.z);
     }
 
-    /// The orthographic size of the camera from the last viewport update
-    pub fn orthographic_size(&self) -> f32 {
-        self.ortho_size
-    }
+    /// Set the camera's `tile_count`, moving the camera so `keep_world_point`
+    /// stays at the same relative position in the camera's view.
+    ///
+    /// If the viewport ends up the same size on screen the point won't move at
+    /// all, otherwise it stays at the same fraction of the viewport.
+    ///
+    /// `keep_world_point` is in the camera's [`WorldSpace`], IE: world pixels
+    /// for [`WorldSpace::Pixels`]. Since only the ratio between the old and
+    /// new tile counts matters, this works the same in either world space.
+    pub fn set_tile_count_around(
+        &mut self,
+        new_count: UVec2,
+        keep_world_point: Vec2,
+        transform: &mut Transform,
+    ) {
+        let old_size = self.grid.world_size();
+        let old_center = transform.translation.truncate() + self.anchor_offset();
 
-    /// Returns an iterator that yields the center of the camera's virtual grid
-    /// tiles in world space.
-    pub fn tile_center_iter(&self, transform: &GlobalTransform) -> impl Iterator<Item = Vec2> {
-        let xy = transform.translation().truncate();
-        self.grid.tile_center_iter().map(move |p| p + xy)
+        self.tile_count = new_count;
+        self.grid.tile_count = new_count;
+
+        let scale = self.grid.world_size() / old_size.max(Vec2::splat(f32::EPSILON));
+        let center = keep_world_point + (old_center - keep_world_point) * scale;
+        let pos = center - self.anchor_offset();
+        transform.translation = pos.extend(transform.translation.z);
     }
 
-    /// Returns an iterator that yields the position of the camera's virtual
-    /// grid tiles in world space.
+    /// Center the camera on the bounding box of `points`.
     ///
-    /// A tile's "position" refers to the bottom left corner of the tile.
-    pub fn tile_pos_iter(&self, cam_transform: &GlobalTransform) -> impl Iterator<Item = Vec2> {
-        let xy = cam_transform.translation().truncate();
-        self.grid.tile_pos_iter().map(move |p| p + xy)
+    /// If `allow_resize` is set, `tile_count` will be grown if needed so all
+    /// the points are visible with at least `padding_tiles` tiles between them
+    /// and the edge of the view. `tile_count` is never shrunk.
+    pub fn frame_points(
+        &mut self,
+        points: &[Vec2],
+        padding_tiles: u32,
+        transform: &mut Transform,
+        allow_resize: bool,
+    ) {
+        let Some(first) = points.first() else {
+            return;
+        };
+        let bounds = points
+            .iter()
+            .fold(Rect::from_corners(*first, *first), |r, p| r.union_point(*p));
+
+        if allow_resize {
+            let tiles = (bounds.size() / self.cell_size_world()).ceil().as_uvec2();
+            let needed = tiles + UVec2::splat(padding_tiles * 2);
+            self.tile_count = self.tile_count.max(needed);
+            self.grid.tile_count = self.tile_count;
+        }
+        let pos = bounds.center() - self.anchor_offset();
+        transform.translation = pos.extend(transform.translation.z);
     }
 
-    /// Transform from world space to camera-local space.
-    pub fn world_to_local(&self, cam_transform: &GlobalTransform, world_pos: impl Point2d) -> Vec2 {
-        world_pos.as_vec2() - cam_transform.translation().truncate()
+    /// Retrieve the target resolution (in pixels) of the camera.
+    ///
+    /// This is [`TiledCamera::target_resolution_override`] if it's set.
+    /// Otherwise it's `pixels_per_tile * tile_count`, where zero `tile_count`
+    /// or `pixels_per_tile` components are treated as 1.
+    pub fn target_resolution(&self) -> UVec2 {
+        match self.target_resolution_override {
+            Some(res) => res.max(UVec2::ONE),
+            None => self.pixels_per_tile.max(UVec2::ONE) * self.tile_count.max(UVec2::ONE),
+        }
     }
 
-    /// Transform from camera-local space to world space.
-    pub fn local_to_world(&self, cam_transform: &GlobalTransform, local_pos: impl Point2d) -> Vec2 {
-        local_pos.as_vec2() + cam_transform.translation().truncate()
+    /// How many tiles a [`ResponsiveMode::Fixed`] camera shows, which is only
+    /// fractional if [`TiledCamera::target_resolution_override`] is set.
+    fn fixed_tiles(&self) -> Vec2 {
+        let tiles = self.grid.tile_count.as_vec2();
+        match self.target_resolution_override {
+            Some(res) => {
+                let ppt = self.pixels_per_tile.max(UVec2::ONE).as_vec2();
+                tiles.min(res.max(UVec2::ONE).as_vec2() / ppt)
+            }
+            None => tiles,
+        }
     }
 
-    /// Convert a world position to it's virtual tile index.
+    /// The largest square `pixels_per_tile` that fits `tile_count` tiles in
+    /// a window of the given size at zoom 1.
     ///
-    /// Tile indices are relative to the camera center.
-    pub fn world_to_index(
-        &self,
-        cam_transform: &GlobalTransform,
-        world_pos: impl Point2d,
-    ) -> IVec2 {
-        let local = self.world_to_local(cam_transform, world_pos);
-        self.grid.pos_to_index(local)
+    /// Both axes use the same size so tiles keep their aspect, the tighter
+    /// axis decides how large they can be. This is never less than 1, even if
+    /// the tiles can't fit.
+    pub fn fit_tiles_to_window(window_size: UVec2, tile_count: UVec2) -> UVec2 {
+        let fit = window_size / tile_count.max(UVec2::ONE);
+        UVec2::splat(fit.min_element().max(1))
     }
 
-    /// Convert a world position to it's virtual tile position.
+    /// Whether the camera's `tile_count` and `pixels_per_tile` are usable,
+    /// IE: none of their components are zero.
     ///
-    /// A tile's "position" refers to the bottom left point of the tile.
-    pub fn world_to_tile(&self, cam_transform: &GlobalTransform, world_pos: impl Point2d) -> Vec2 {
-        let local = self.world_to_local(cam_transform, world_pos);
-        self.grid.pos_to_tile_pos(local)
+    /// Invalid components are treated as 1 when updating the viewport.
+    pub fn is_valid(&self) -> bool {
+        self.tile_count.cmpgt(UVec2::ZERO).all() && self.pixels_per_tile.cmpgt(UVec2::ZERO).all()
     }
 
-    /// Convert a tile index to it's virtual tile position in world space.
+    /// The size of a pixel exact screenshot of the camera's view, IE: the
+    /// native un-upscaled resolution of the viewport, from the last viewport
+    /// update.
     ///
-    /// Tiles indices are relative to the camera center.
+    /// This is the target resolution unless the viewport shows extra tiles
+    /// ([`ResponsiveMode::MinTiles`]) or is cropped because the window is too
+    /// small to fit the target resolution at the camera's zoom. Before the
+    /// first viewport update it's the target resolution.
     ///
-    /// A tile's "position" refers to the bottom left point of the tile.
-    pub fn index_to_tile_pos(&self, cam_transform: &GlobalTransform, pos: impl GridPoint) -> Vec2 {
-        let p = self.grid.index_to_pos(pos);
-        self.local_to_world(cam_transform, p)
+    /// Bevy's `ScreenshotManager` captures the whole window, letterboxing and
+    /// all. To get a pixel exact image crop the capture to the camera's
+    /// viewport ([`TiledCamera::viewport_pos`] and [`TiledCamera::viewport_size`])
+    /// and downscale it by [`TiledCamera::zoom`] with nearest filtering, which
+    /// results in an image of this size.
+    pub fn screenshot_image_size(&self) -> UVec2 {
+        if !self.initialized {
+            return self.target_resolution();
+        }
+        (self.vp_size.as_vec2() / self.zoom)
+            .round()
+            .as_uvec2()
+            .max(UVec2::ONE)
     }
 
-    /// Return the world center of the virtual tile at the given tile index.
+    /// The resolution the camera is currently rendering at before being
+    /// scaled up, in pixels.
     ///
-    /// Tile indices are relative to the camera center.
-    pub fn index_to_tile_center(
-        &self,
-        cam_transform: &GlobalTransform,
-        index: impl GridPoint,
-    ) -> Vec2 {
-        let p = self.grid.index_to_tile_center(index);
-        self.local_to_world(cam_transform, p)
+    /// This is the target resolution unless the camera's [`ResponsiveMode`]
+    /// is showing extra tiles.
+    pub fn effective_resolution(&self) -> Vec2 {
+        match self.responsive_mode {
+            ResponsiveMode::Fixed | ResponsiveMode::FlexibleWidth => {
+                self.fixed_tiles() * self.pixels_per_tile.as_vec2()
+            }
+            ResponsiveMode::MinTiles => self.vp_size.as_vec2() / self.zoom,
+        }
     }
 
-    /// Change the camera's [`WorldSpace`].
-    pub fn set_world_space(&mut self, world_space: WorldSpace) {
-        self.grid.world_space = world_space;
+    /// How the camera's effective resolution relates to it's
+    /// [`TiledCamera::design_resolution`] on each axis.
+    ///
+    /// IE: `[1.5, 1.0]` means the camera is showing 50% more pixels
+    /// horizontally than the UI was designed for.
+    pub fn design_scale(&self) -> Vec2 {
+        let design = self
+            .design_resolution
+            .unwrap_or_else(|| self.target_resolution());
+        self.effective_resolution() / design.max(UVec2::ONE).as_vec2()
     }
 
-    /// Get the camera's [`WorldSpace`].
-    pub fn world_space(&self) -> WorldSpace {
-        self.grid.world_space
+    /// The value to use for bevy's `UiScale` so that one UI pixel matches one
+    /// upscaled game pixel, given the window's scale factor.
+    pub fn recommended_ui_scale(&self, window_scale_factor: f64) -> f32 {
+        (self.zoom as f64 / window_scale_factor) as f32
     }
 
-    /// Get unit size or [`None`], depending on the camera's [`WorldSpace`].
-    ///
-    /// This can be used for sizing spawned sprites. If the camera's [`WorldSpace`]
-    /// is [`WorldSpace::Units`] then a unit sized sprite should be the size of
-    /// a tile.
-    /// Otherwise it should use the default sprite size, which is the pixel dimensions
-    /// of the sprite's texture.
-    pub fn unit_size(&self) -> Option<Vec2> {
-        match self.grid.world_space {
-            WorldSpace::Units => Some(self.grid.tile_size_world()),
-            WorldSpace::Pixels => None,
+    /// The integer zoom the camera would use for an area of size `wres`.
+    fn zoom_for(&self, wres: UVec2) -> u32 {
+        match self.forced_zoom {
+            Some(zoom) => zoom.max(1),
+            None => {
+                let ratio = wres / self.target_resolution().max(UVec2::ONE);
+                let zoom = match self.scale_axis {
+                    ScaleAxis::Vertical => ratio.y,
+                    ScaleAxis::Horizontal => ratio.x,
+                    ScaleAxis::Auto => ratio.min_element(),
+                };
+                zoom.max(self.min_zoom.max(1))
+            }
         }
     }
 
-    /// How much the camera view is scaled up, based on target resolution and window size.
-    pub fn zoom(&self) -> u32 {
-        self.zoom
+    /// The zoom the camera would use for an area of size `wres`, which will
+    /// only be fractional if [`TiledCamera::integer_scaling`] is disabled.
+    fn zoom_for_f32(&self, wres: Vec2) -> f32 {
+        if self.integer_scaling || self.forced_zoom.is_some() {
+            return self.zoom_for(wres.as_uvec2()) as f32;
+        }
+        let ratio = wres / self.target_resolution().max(UVec2::ONE).as_vec2();
+        let zoom = match self.scale_axis {
+            ScaleAxis::Vertical => ratio.y,
+            ScaleAxis::Horizontal => ratio.x,
+            ScaleAxis::Auto => ratio.min_element(),
+        };
+        zoom.max(self.min_zoom.max(1) as f32)
     }
 
-    // MIT License
-    // Copyright (c) 2021 Aevyrie
-    // https://github.com/aevyrie/bevy_mod_raycast
-    /// Convert a screen position (IE: The mouse cursor position) to it's corresponding world position.
-    pub fn screen_to_world(
-        &self,
-        screen_pos: Vec2,
-        camera: &Camera,
-        camera_transform: &GlobalTransform,
-    ) -> Option<Vec2> {
-        let screen_size = self.vp_size.as_vec2();
-        let screen_pos = (screen_pos - self.vp_pos.as_vec2()).round();
+    /// The axis the camera scales along for an area of size `wres`.
+    ///
+    /// [`ScaleAxis::Auto`] picks whichever axis has the least room to spare.
+    fn resolved_scale_axis(&self, wres: UVec2) -> ScaleAxis {
+        match self.scale_axis {
+            ScaleAxis::Auto => {
+                let ratio = wres.as_vec2() / self.target_resolution().max(UVec2::ONE).as_vec2();
+                if ratio.x < ratio.y {
+                    ScaleAxis::Horizontal
+                } else {
+                    ScaleAxis::Vertical
+                }
+            }
+            axis => axis,
+        }
+    }
 
-        let view = camera_transform.compute_matrix();
-        let projection = camera.projection_matrix();
+    /// The viewport the camera would use for a render target of size
+    /// `target_size`, as `(viewport_pos, viewport_size, zoom)`.
+    ///
+    /// This doesn't modify the camera. The viewport position is counted from
+    /// the top left of the render target.
+    pub fn compute_viewport_for(&self, target_size: UVec2) -> (UVec2, UVec2, u32) {
+        let layout = viewport_layout(self, target_size);
+        (
+            layout.pos.as_uvec2(),
+            layout.size.as_uvec2(),
+            layout.zoom.max(1.0) as u32,
+        )
+    }
 
-        // 2D Normalized device coordinate cursor position from (-1, -1) to (1, 1)
-        let cursor_ndc = (screen_pos / screen_size) * 2.0 - Vec2::from([1.0, 1.0]);
-        let ndc_to_world: Mat4 = view * projection.inverse();
-        let world_to_ndc = projection * view;
+    /// The integer zoom for a window of size `window_res`, along with the
+    /// number of window pixels left over on each axis after scaling the
+    /// target resolution by that zoom.
+    ///
+    /// The leftover pixels are the total thickness of the letterbox bars on
+    /// each axis.
+    pub fn zoom_and_remainder(&self, window_res: UVec2) -> (u32, UVec2) {
+        let zoom = self.zoom_for(window_res);
+        let remainder = window_res.saturating_sub(self.target_resolution() * zoom);
+        (zoom, remainder)
+    }
+
+    /// The size of the image the camera renders to when it's
+    /// [`TiledCamera::render_scale`] is below 1, from the last viewport
+    /// update.
+    ///
+    /// This is the viewport size scaled by the render scale, which is clamped
+    /// from 0 to 1. The image is always at least one pixel wide and tall.
+    pub fn render_image_size(&self) -> UVec2 {
+        (self.vp_size.as_vec2() * self.render_scale.clamp(0.0, 1.0))
+            .round()
+            .as_uvec2()
+            .max(UVec2::ONE)
+    }
+
+    /// The render target and order the camera's view is drawn to the window
+    /// with, which differ from the camera's own while it renders to an image
+    /// for [`TiledCamera::render_scale`].
+    pub(crate) fn output<'a>(&'a self, cam: &'a Camera) -> (&'a RenderTarget, isize) {
+        match &self.scaled_output {
+            Some((target, order)) => (target, *order),
+            None => (&cam.target, cam.order),
+        }
+    }
+
+    /// How many render target pixels a single tile spans, from the last
+    /// viewport update.
+    pub fn tile_render_size(&self) -> UVec2 {
+        (self.pixels_per_tile.as_vec2() * self.zoom).as_uvec2()
+    }
+
+    /// The viewport size in physical pixels, from the last viewport update.
+    ///
+    /// Viewports are updated in [`PostUpdate`], so after changing the camera
+    /// this is stale until the update runs.
+    pub fn viewport_size(&self) -> UVec2 {
+        self.vp_size
+    }
+
+    /// The position of the top left of the viewport in physical pixels, from
+    /// the last viewport update.
+    ///
+    /// Like bevy's [`Viewport`] this is counted from the top left of the
+    /// window, unlike screen positions which count from the bottom left.
+    /// Viewports are updated in [`PostUpdate`], so after changing the camera
+    /// this is stale until the update runs.
+    pub fn viewport_pos(&self) -> UVec2 {
+        self.vp_pos
+    }
+
+    /// The viewport rect in physical pixels, from the last viewport update.
+    ///
+    /// The rect's `min` is the viewport's top left corner. Viewports are
+    /// updated in [`PostUpdate`], so after changing the camera this is stale
+    /// until the update runs.
+    pub fn viewport_rect(&self) -> Rect {
+        let min = self.vp_pos.as_vec2();
+        Rect::from_corners(min, min + self.vp_size.as_vec2())
+    }
+
+    /// Whether a screen position falls inside the viewport from the last
+    /// viewport update, as opposed to the letterbox bars around it.
+    ///
+    /// Screen positions are in physical pixels, counted from the bottom left
+    /// of the window.
+    pub fn screen_in_viewport(&self, screen_pos: Vec2) -> bool {
+        let p = screen_pos - self.viewport_screen_min();
+        p.cmpge(Vec2::ZERO).all() && p.cmplt(self.vp_size.as_vec2()).all()
+    }
+
+    /// The bottom left corner of the viewport in screen space, IE: physical
+    /// pixels counted from the bottom left of the window.
+    fn viewport_screen_min(&self) -> Vec2 {
+        let top = (self.vp_pos.y + self.vp_size.y) as f32;
+        Vec2::new(self.vp_pos.x as f32, self.win_size.y as f32 - top)
+    }
+
+    /// The fraction of the window covered by the viewport from the last
+    /// viewport update, from 0 to 1.
+    ///
+    /// Any part of the viewport that extends outside the window is ignored.
+    pub fn coverage(&self, window_res: UVec2) -> f32 {
+        let window_area = window_res.as_vec2().x * window_res.as_vec2().y;
+        if window_area <= 0.0 {
+            return 0.0;
+        }
+        let min = self.vp_pos.min(window_res);
+        let max = (self.vp_pos + self.vp_size).min(window_res);
+        let size = (max - min).as_vec2();
+        size.x * size.y / window_area
+    }
+
+    /// The window pixel rects of the letterbox bars around the viewport from
+    /// the last viewport update, in the order left, right, top, bottom.
+    ///
+    /// Window pixels are counted from the top left of the window. Bars are
+    /// only returned if they're not empty, and only cover the camera's
+    /// `window_region`.
+    pub fn letterbox_rects(&self, window_res: UVec2) -> Vec<Rect> {
+        self.letterbox_bars(window_res)
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect()
+    }
+
+    /// The letterbox bars in the order left, right, top, bottom, including
+    /// any empty bars.
+    fn letterbox_bars(&self, window_res: UVec2) -> [Rect; 4] {
+        let wres = window_res.as_vec2();
+        let region_min = (self.window_region.min * wres).floor();
+        let region_max = (self.window_region.max * wres).floor();
+        let vp_min = self.vp_pos.as_vec2().clamp(region_min, region_max);
+        let vp_max = (self.vp_pos + self.vp_size)
+            .as_vec2()
+            .clamp(region_min, region_max);
+
+        [
+            Rect::from_corners(region_min, Vec2::new(vp_min.x, region_max.y)),
+            Rect::from_corners(Vec2::new(vp_max.x, region_min.y), region_max),
+            Rect::from_corners(
+                Vec2::new(vp_min.x, region_min.y),
+                Vec2::new(vp_max.x, vp_min.y),
+            ),
+            Rect::from_corners(
+                Vec2::new(vp_min.x, vp_max.y),
+                Vec2::new(vp_max.x, region_max.y),
+            ),
+        ]
+    }
+
+    /// Whether the camera's viewport has been set up. This will be false until
+    /// the camera's window exists.
+    ///
+    /// Until the camera is initialized the viewport values and any conversions
+    /// that rely on them are invalid.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Window resolution from the last viewport update
+    pub fn window_resolution(&self) -> UVec2 {
+        self.win_size
+    }
+
+    /// The orthographic size of the camera from the last viewport update.
+    ///
+    /// This is the size of the view along the axis chosen by the camera's
+    /// [`ScaleAxis`].
+    pub fn orthographic_size(&self) -> f32 {
+        self.ortho_size
+    }
+
+    /// The world position of the camera before `render_offset` was applied,
+    /// from the last time the camera's transform was updated.
+    ///
+    /// The camera's [`GlobalTransform`] will include the render offset, this
+    /// is the position gameplay code should use.
+    pub fn logical_position(&self) -> Vec2 {
+        self.logical_pos
+    }
+
+    /// The size of the camera's view in world space.
+    ///
+    /// Depending on the camera's [`ResponsiveMode`] this may be larger than
+    /// `tile_count` tiles.
+    pub fn visible_world_size(&self) -> Vec2 {
+        let tiles = match self.responsive_mode {
+            ResponsiveMode::Fixed | ResponsiveMode::FlexibleWidth => {
+                let tiles = self.fixed_tiles();
+                if self.initialized {
+                    tiles.min(self.vp_size.as_vec2() / (self.pixels_per_tile.as_vec2() * self.zoom))
+                } else {
+                    tiles
+                }
+            }
+            ResponsiveMode::MinTiles => {
+                self.vp_size.as_vec2() / (self.pixels_per_tile.as_vec2() * self.zoom)
+            }
+        };
+        tiles * self.cell_size_world()
+    }
+
+    /// The size of the camera's view in world space for a render target of
+    /// size `target_size`.
+    ///
+    /// This doesn't modify the camera, see [`TiledCamera::compute_viewport_for`].
+    pub fn visible_world_size_for(&self, target_size: UVec2) -> Vec2 {
+        viewport_layout(self, target_size).visible_tiles * self.cell_size_world()
+    }
+
+    /// The number of whole tiles that fit in the viewport on each axis, from
+    /// the last viewport update.
+    ///
+    /// Unlike `tile_count` this accounts for any extra tiles revealed by the
+    /// camera's [`ResponsiveMode`], IE: with [`ResponsiveMode::MinTiles`] and
+    /// [`ScaleAxis::Vertical`] it may be larger than `tile_count` on the
+    /// horizontal axis. Partially visible tiles aren't counted.
+    pub fn visible_tile_count(&self) -> UVec2 {
+        let tile_px = self.pixels_per_tile.max(UVec2::ONE).as_vec2() * self.zoom;
+        // The viewport size is rounded to whole pixels, allow for a little
+        // error so exactly fitting tiles aren't dropped.
+        (self.vp_size.as_vec2() / tile_px + 1e-3).floor().as_uvec2()
+    }
+
+    /// The world space rects the camera would show if the window had each of
+    /// the given aspect ratios (width / height), keeping the current window
+    /// height.
+    pub fn aspect_view_rects(&self, cam_transform: &GlobalTransform, aspects: &[f32]) -> Vec<Rect> {
+        let center = self.view_center(cam_transform);
+        let height = self.win_size.y.max(1) as f32;
+        aspects
+            .iter()
+            .map(|aspect| {
+                let size = Vec2::new((height * aspect).round().max(1.0), height);
+                Rect::from_center_size(center, self.visible_world_size_for(size.as_uvec2()))
+            })
+            .collect()
+    }
+
+    /// The world space rect that's visible for every one of the given aspect
+    /// ratios, IE: the area where important content should be placed.
+    ///
+    /// See [`TiledCamera::aspect_view_rects`].
+    pub fn safe_zone(&self, cam_transform: &GlobalTransform, aspects: &[f32]) -> Rect {
+        self.aspect_view_rects(cam_transform, aspects)
+            .into_iter()
+            .fold(self.visible_world_rect(cam_transform), |a, b| {
+                a.intersect(b)
+            })
+    }
+
+    /// The aspect ratio (width / height) of the camera's view in world
+    /// space.
+    ///
+    /// This accounts for the camera's [`ResponsiveMode`], so it may differ
+    /// from the aspect ratio of the target resolution.
+    pub fn visible_aspect(&self) -> f32 {
+        let size = self.visible_world_size();
+        size.x / size.y
+    }
+
+    /// The offset from the camera's translation to the center of it's view.
+    fn anchor_offset(&self) -> Vec2 {
+        (Vec2::splat(0.5) - self.camera_anchor) * self.visible_world_size()
+    }
+
+    /// The world position of the center of the camera's view.
+    fn view_center(&self, cam_transform: &GlobalTransform) -> Vec2 {
+        debug_assert!(
+            is_axis_aligned(cam_transform),
+            "TiledCamera doesn't support rotation, the camera's transform must be axis aligned"
+        );
+        cam_transform.translation().truncate() + self.anchor_offset()
+    }
+
+    /// The world space rect visible to the camera.
+    ///
+    /// The rect is in world units for [`WorldSpace::Units`] and world pixels
+    /// for [`WorldSpace::Pixels`].
+    pub fn visible_world_rect(&self, cam_transform: &GlobalTransform) -> Rect {
+        let center = self.view_center(cam_transform);
+        Rect::from_center_size(center, self.visible_world_size())
+    }
+
+    /// Whether a world position is inside the area visible to the camera.
+    ///
+    /// `world_pos` is in world units for [`WorldSpace::Units`] and world
+    /// pixels for [`WorldSpace::Pixels`]. See [`TiledCamera::visible_world_rect`].
+    pub fn contains_world_point(&self, cam_transform: &GlobalTransform, world_pos: Vec2) -> bool {
+        self.visible_world_rect(cam_transform).contains(world_pos)
+    }
+
+    /// Split the position of the center of the camera's view into the index
+    /// of the world tile it's in and it's offset within that tile.
+    ///
+    /// World tiles are aligned to the world origin, so tile `[0,0]` has it's
+    /// bottom left corner at `[0,0]`. The offset is in world space, from zero
+    /// up to the size of a tile (see [`TiledCamera::cell_size_world`]). This
+    /// can be used to scroll a tilemap by whole tiles and offset it by the
+    /// remainder.
+    pub fn split_tile_offset(&self, cam_transform: &GlobalTransform) -> (IVec2, Vec2) {
+        let cell = self.cell_size_world();
+        let center = self.view_center(cam_transform);
+        let index = (center / cell).floor();
+        (index.as_ivec2(), center - index * cell)
+    }
+
+    /// Whether `world_rect` is fully, partially, or not at all inside the
+    /// camera's view.
+    ///
+    /// A rect that only touches the edge of the view is not visible.
+    pub fn rect_visibility(
+        &self,
+        cam_transform: &GlobalTransform,
+        world_rect: &Rect,
+    ) -> RectVisibility {
+        let view = self.visible_world_rect(cam_transform);
+        if view.intersect(*world_rect).is_empty() {
+            RectVisibility::None
+        } else if view.union(*world_rect) == view {
+            RectVisibility::Fully
+        } else {
+            RectVisibility::Partial
+        }
+    }
+
+    /// Map a normalized viewport coordinate to a world position, where `[0,0]`
+    /// is the bottom left of the camera's view and `[1,1]` is the top right.
+    pub fn viewport_uv_to_world(&self, uv: Vec2, cam_transform: &GlobalTransform) -> Vec2 {
+        let bounds = self.visible_world_rect(cam_transform);
+        bounds.min + uv * bounds.size()
+    }
+
+    /// The visible world space rect expanded by `margin_tiles` tiles on every
+    /// side.
+    ///
+    /// Content inside this rect can be loaded ahead of time so it's ready
+    /// before it scrolls into view.
+    pub fn stream_bounds(&self, cam_transform: &GlobalTransform, margin_tiles: u32) -> Rect {
+        let bounds = self.visible_world_rect(cam_transform);
+        let margin = self.cell_size_world() * margin_tiles as f32;
+        Rect::from_corners(bounds.min - margin, bounds.max + margin)
+    }
+
+    /// Returns an iterator that yields the index of every chunk that overlaps
+    /// the camera's view, given the size of a chunk in tiles.
+    ///
+    /// Unlike tile indices, chunk indices are in world space - chunk `[0,0]`
+    /// starts at the world origin.
+    pub fn visible_chunks(
+        &self,
+        cam_transform: &GlobalTransform,
+        chunk_size: UVec2,
+    ) -> impl Iterator<Item = IVec2> {
+        let tile_size = self.cell_size_world();
+        let bounds = self.visible_world_rect(cam_transform);
+        let min_tile = (bounds.min / tile_size).floor().as_ivec2();
+        let max_tile = (bounds.max / tile_size).ceil().as_ivec2() - IVec2::ONE;
+        let chunk_size = chunk_size.max(UVec2::ONE).as_ivec2();
+        let min = min_tile.div_euclid(chunk_size);
+        let max = max_tile.div_euclid(chunk_size);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+    }
+
+    /// The world positions of the midpoints of the edges of the camera's view,
+    /// in the order `[left, right, bottom, top]`.
+    pub fn edge_midpoints_world(&self, cam_transform: &GlobalTransform) -> [Vec2; 4] {
+        let center = self.view_center(cam_transform);
+        let half = self.visible_world_size() / 2.0;
+        [
+            center - Vec2::new(half.x, 0.0),
+            center + Vec2::new(half.x, 0.0),
+            center - Vec2::new(0.0, half.y),
+            center + Vec2::new(0.0, half.y),
+        ]
+    }
+
+    /// The range of world positions the camera's translation can move within
+    /// while keeping it's view inside `bounds`.
+    ///
+    /// Returns [`None`] if `bounds` is not set or if the view is larger than
+    /// `bounds` on either axis, in which case the center of the view is fixed
+    /// to the center of `bounds` on that axis.
+    pub fn pan_limits(&self) -> Option<Rect> {
+        let bounds = self.bounds?;
+        let half = self.visible_world_size() / 2.0;
+        if bounds.half_size().cmplt(half).any() {
+            return None;
+        }
+        let offset = self.anchor_offset();
+        Some(Rect::from_corners(
+            bounds.min + half - offset,
+            bounds.max - half - offset,
+        ))
+    }
+
+    /// Clamp a camera translation so the camera's view (see
+    /// [`TiledCamera::visible_world_rect`]) stays inside `world_bounds`.
+    ///
+    /// If the view is larger than `world_bounds` on an axis it's centered on
+    /// that axis instead. This is the same clamping used for
+    /// [`TiledCamera::bounds`], and can be used to constrain player panning.
+    pub fn clamp_translation_to_bounds(&self, translation: Vec2, world_bounds: Rect) -> Vec2 {
+        let half = self.visible_world_size() / 2.0;
+        let min = world_bounds.min + half;
+        let max = world_bounds.max - half;
+        let offset = self.anchor_offset();
+        let center = translation + offset;
+        Vec2::select(
+            min.cmple(max),
+            center.clamp(min, max.max(min)),
+            world_bounds.center(),
+        ) - offset
+    }
+
+    /// The range of tile indices covered by the camera's grid, relative to the
+    /// camera's [`CameraOrigin`]. Both `min` and `max` are inclusive.
+    fn index_range(&self) -> (IVec2, IVec2) {
+        let count = self.grid.tile_count.as_ivec2();
+        let min = match self.origin {
+            CameraOrigin::Center => -(count / 2),
+            CameraOrigin::BottomLeft => IVec2::ZERO,
+        };
+        (min, min + count - IVec2::ONE)
+    }
+
+    /// Whether `index` is inside the camera's grid.
+    fn index_in_bounds(&self, index: IVec2) -> bool {
+        let (min, max) = self.index_range();
+        index.cmpge(min).all() && index.cmple(max).all()
+    }
+
+    /// The camera-local position of the bottom left corner of the tile at
+    /// `index`.
+    fn index_to_local_pos(&self, index: IVec2) -> Vec2 {
+        let (min, _) = self.index_range();
+        let grid_min = -self.grid.world_size() / 2.0;
+        grid_min + (index - min).as_vec2() * self.cell_size_world()
+    }
+
+    /// The camera-local position of the center of the tile at `index`.
+    fn index_to_local_center(&self, index: IVec2) -> Vec2 {
+        self.index_to_local_pos(index) + self.cell_size_world() / 2.0
+    }
+
+    /// The world space rect covered by the given row of the camera's grid.
+    ///
+    /// Rows are indexed relative to the camera's [`CameraOrigin`], the same
+    /// as tile indices. Returns [`None`] if the row is out of the camera's view.
+    pub fn row_rect_world(&self, cam_transform: &GlobalTransform, row: i32) -> Option<Rect> {
+        let (min, max) = self.index_range();
+        if row < min.y || row > max.y {
+            return None;
+        }
+        let xy = self.view_center(cam_transform);
+        let bl = self.index_to_local_pos(IVec2::new(min.x, row)) + xy;
+        let size = Vec2::new(self.grid.world_size().x, self.cell_size_world().y);
+        Some(Rect::from_corners(bl, bl + size))
+    }
+
+    /// The world space rect covered by the given column of the camera's grid.
+    ///
+    /// Columns are indexed relative to the camera's [`CameraOrigin`], the
+    /// same as tile indices. Returns [`None`] if the column is out of the camera's view.
+    pub fn column_rect_world(&self, cam_transform: &GlobalTransform, column: i32) -> Option<Rect> {
+        let (min, max) = self.index_range();
+        if column < min.x || column > max.x {
+            return None;
+        }
+        let xy = self.view_center(cam_transform);
+        let bl = self.index_to_local_pos(IVec2::new(column, min.y)) + xy;
+        let size = Vec2::new(self.cell_size_world().x, self.grid.world_size().y);
+        Some(Rect::from_corners(bl, bl + size))
+    }
+
+    /// The world space centers of every tile in the given row of the camera's
+    /// grid, from left to right.
+    ///
+    /// Rows are indexed relative to the camera's [`CameraOrigin`], the same
+    /// as tile indices. Returns [`None`] if the row is out of the camera's view.
+    pub fn row_centers_world(
+        &self,
+        cam_transform: &GlobalTransform,
+        row: i32,
+    ) -> Option<Vec<Vec2>> {
+        let (min, max) = self.index_range();
+        if row < min.y || row > max.y {
+            return None;
+        }
+        let xy = self.view_center(cam_transform);
+        let centers = (min.x..=max.x)
+            .map(|x| self.index_to_local_center(IVec2::new(x, row)) + xy)
+            .collect();
+        Some(centers)
+    }
+
+    /// The world space centers of every tile in the given column of the
+    /// camera's grid, from bottom to top.
+    ///
+    /// Columns are indexed relative to the camera's [`CameraOrigin`], the
+    /// same as tile indices. Returns [`None`] if the column is out of the camera's view.
+    pub fn column_centers_world(
+        &self,
+        cam_transform: &GlobalTransform,
+        column: i32,
+    ) -> Option<Vec<Vec2>> {
+        let (min, max) = self.index_range();
+        if column < min.x || column > max.x {
+            return None;
+        }
+        let xy = self.view_center(cam_transform);
+        let centers = (min.y..=max.y)
+            .map(|y| self.index_to_local_center(IVec2::new(column, y)) + xy)
+            .collect();
+        Some(centers)
+    }
+
+    /// Compute which tiles become visible and which stop being visible when
+    /// the camera's origin tile, at index `[0,0]` (see [`CameraOrigin`]),
+    /// moves from `old_center` to `new_center`.
+    ///
+    /// Returns `(entered, exited)` tile indices, given the current `tile_count`.
+    pub fn visibility_delta(
+        &self,
+        old_center: IVec2,
+        new_center: IVec2,
+    ) -> (Vec<IVec2>, Vec<IVec2>) {
+        let (min, max) = self.index_range();
+        let contains = |center: IVec2, i: IVec2| {
+            let i = i - center;
+            i.cmpge(min).all() && i.cmple(max).all()
+        };
+        let outside = |from: IVec2, to: IVec2| {
+            (min.y..=max.y)
+                .flat_map(move |y| (min.x..=max.x).map(move |x| from + IVec2::new(x, y)))
+                .filter(move |&i| !contains(to, i))
+                .collect::<Vec<_>>()
+        };
+        (
+            outside(new_center, old_center),
+            outside(old_center, new_center),
+        )
+    }
+
+    /// The extents of the camera's orthographic projection in camera-local
+    /// space, from the last viewport update.
+    ///
+    /// The extents are offset so the camera's position is at it's
+    /// [`TiledCamera::camera_anchor`].
+    pub fn ortho_extents(&self) -> Rect {
+        let size = self.visible_world_size();
+        Rect::from_corners(
+            -self.camera_anchor * size,
+            (1.0 - self.camera_anchor) * size,
+        )
+    }
+
+    /// A snapshot of the camera's projection as a fully specified
+    /// [`OrthographicProjection`] with explicit extents.
+    ///
+    /// Near and far planes are the ones set with
+    /// [`TiledCameraBundle::with_depth_range`], or the defaults from
+    /// [`Camera2dBundle`]. Changes made directly to the camera's projection
+    /// aren't tracked.
+    pub fn as_orthographic(&self) -> OrthographicProjection {
+        let extents = self.ortho_extents();
+        let (near, far) = self.depth_range;
+        OrthographicProjection {
+            near,
+            far,
+            viewport_origin: self.camera_anchor,
+            scaling_mode: ScalingMode::Fixed {
+                width: extents.width(),
+                height: extents.height(),
+            },
+            area: extents,
+            ..default()
+        }
+    }
+
+    /// Returns an iterator that yields the center of the camera's virtual grid
+    /// tiles in world space.
+    pub fn tile_center_iter(&self, transform: &GlobalTransform) -> impl Iterator<Item = Vec2> {
+        let xy = self.view_center(transform);
+        self.grid.tile_center_iter().map(move |p| p + xy)
+    }
+
+    /// Returns an iterator that yields the position of the camera's virtual
+    /// grid tiles in world space.
+    ///
+    /// A tile's "position" refers to the bottom left corner of the tile.
+    pub fn tile_pos_iter(&self, cam_transform: &GlobalTransform) -> impl Iterator<Item = Vec2> {
+        let xy = self.view_center(cam_transform);
+        self.grid.tile_pos_iter().map(move |p| p + xy)
+    }
+
+    /// Returns an iterator that yields the index of every tile in the
+    /// camera's virtual grid, row by row from the bottom left.
+    ///
+    /// Tile indices are relative to the camera's [`CameraOrigin`], the same
+    /// as [`TiledCamera::world_to_index`].
+    pub fn tile_index_iter(&self) -> impl Iterator<Item = IVec2> {
+        let (min, max) = self.index_range();
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+    }
+
+    /// Returns an iterator that yields the index and world space position of
+    /// every tile in the camera's virtual grid, row by row from the bottom
+    /// left.
+    ///
+    /// A tile's "position" refers to the bottom left corner of the tile.
+    pub fn tile_index_pos_iter(
+        &self,
+        cam_transform: &GlobalTransform,
+    ) -> impl Iterator<Item = (IVec2, Vec2)> + '_ {
+        let xy = self.view_center(cam_transform);
+        self.tile_index_iter()
+            .map(move |i| (i, self.index_to_local_pos(i) + xy))
+    }
+
+    /// Returns an iterator that yields the index and world space center of
+    /// every tile on the outer edge of the camera's virtual grid.
+    ///
+    /// Tiles are yielded in counter-clockwise order starting from the bottom
+    /// left tile. Each tile is only yielded once.
+    pub fn border_tile_iter(
+        &self,
+        cam_transform: &GlobalTransform,
+    ) -> impl Iterator<Item = (IVec2, Vec2)> + '_ {
+        let xy = self.view_center(cam_transform);
+        let (min, max) = self.index_range();
+        let bottom = (min.x..=max.x).map(move |x| IVec2::new(x, min.y));
+        let right = (min.y + 1..=max.y).map(move |y| IVec2::new(max.x, y));
+        let top = (min.x..max.x)
+            .rev()
+            .filter(move |_| max.y > min.y)
+            .map(move |x| IVec2::new(x, max.y));
+        let left = (min.y + 1..max.y)
+            .rev()
+            .filter(move |_| max.x > min.x)
+            .map(move |y| IVec2::new(min.x, y));
+        bottom
+            .chain(right)
+            .chain(top)
+            .chain(left)
+            .map(move |i| (i, self.index_to_local_center(i) + xy))
+    }
+
+    /// The world space translation needed to scroll the camera by `dir`
+    /// tiles.
+    ///
+    /// Add the result to the camera's [`Transform`] to move it.
+    pub fn scroll_by_tiles(&self, dir: IVec2) -> Vec2 {
+        dir.as_vec2() * self.cell_size_world()
+    }
+
+    /// The camera's world position measured in tiles.
+    pub fn position_in_tiles(&self, cam_transform: &GlobalTransform) -> Vec2 {
+        cam_transform.translation().truncate() / self.cell_size_world()
+    }
+
+    /// Returns an iterator that yields a world space line segment for every
+    /// tile boundary of the camera's virtual grid.
+    ///
+    /// Vertical lines are yielded first, from left to right, followed by
+    /// horizontal lines from bottom to top.
+    pub fn grid_lines_world(
+        &self,
+        cam_transform: &GlobalTransform,
+    ) -> impl Iterator<Item = (Vec2, Vec2)> {
+        let tile_size = self.cell_size_world();
+        let count = self.grid.tile_count;
+        let Rect { min, max } = self.grid_world_rect(cam_transform);
+        let vertical = (0..=count.x).map(move |x| {
+            let x = min.x + x as f32 * tile_size.x;
+            (Vec2::new(x, min.y), Vec2::new(x, max.y))
+        });
+        let horizontal = (0..=count.y).map(move |y| {
+            let y = min.y + y as f32 * tile_size.y;
+            (Vec2::new(min.x, y), Vec2::new(max.x, y))
+        });
+        vertical.chain(horizontal)
+    }
+
+    /// The world space rect covered by the camera's virtual grid.
+    fn grid_world_rect(&self, cam_transform: &GlobalTransform) -> Rect {
+        Rect::from_center_size(self.view_center(cam_transform), self.grid.world_size())
+    }
+
+    /// Transform from world space to camera-local space.
+    pub fn world_to_local(&self, cam_transform: &GlobalTransform, world_pos: impl Point2d) -> Vec2 {
+        world_pos.as_vec2() - self.view_center(cam_transform)
+    }
+
+    /// Transform from camera-local space to world space.
+    pub fn local_to_world(&self, cam_transform: &GlobalTransform, local_pos: impl Point2d) -> Vec2 {
+        local_pos.as_vec2() + self.view_center(cam_transform)
+    }
+
+    /// Convert a world position to it's virtual tile index.
+    ///
+    /// Tile indices are relative to the camera's [`CameraOrigin`].
+    pub fn world_to_index(
+        &self,
+        cam_transform: &GlobalTransform,
+        world_pos: impl Point2d,
+    ) -> IVec2 {
+        self.world_to_tile(cam_transform, world_pos)
+            .floor()
+            .as_ivec2()
+    }
+
+    /// Convert a world position to it's virtual tile index, or [`None`] if
+    /// the position is outside the camera's virtual grid.
+    ///
+    /// Tile indices are relative to the camera's [`CameraOrigin`].
+    pub fn world_to_index_checked(
+        &self,
+        cam_transform: &GlobalTransform,
+        world_pos: impl Point2d,
+    ) -> Option<IVec2> {
+        let index = self.world_to_index(cam_transform, world_pos);
+        self.index_in_bounds(index).then_some(index)
+    }
+
+    /// Returns an iterator that yields the index of every tile a world space
+    /// line passes through, in order from `world_start` to `world_end`.
+    ///
+    /// Tiles are visited one axis at a time, except when the line passes
+    /// exactly through a tile corner, where it steps diagonally. Tile indices
+    /// are relative to the camera's [`CameraOrigin`], the same as
+    /// [`TiledCamera::world_to_index`].
+    pub fn tiles_along_line(
+        &self,
+        cam_transform: &GlobalTransform,
+        world_start: Vec2,
+        world_end: Vec2,
+    ) -> impl Iterator<Item = IVec2> {
+        // Positions measured in tiles, where tile `[0,0]` spans `[0,0]..[1,1]`
+        let origin = self.view_center(cam_transform) + self.index_to_local_pos(IVec2::ZERO);
+        let cell = self.cell_size_world();
+        let start = (world_start - origin) / cell;
+        let end = (world_end - origin) / cell;
+
+        let mut current = start.floor().as_ivec2();
+        let last = end.floor().as_ivec2();
+        let dir = end - start;
+        let step = IVec2::new(dir.x.signum() as i32, dir.y.signum() as i32)
+            * IVec2::select(dir.cmpeq(Vec2::ZERO), IVec2::ZERO, IVec2::ONE);
+        // The distance along the line to the next tile boundary on each axis,
+        // and between boundaries, as a fraction of the line's length.
+        let next_boundary = current.as_vec2() + step.max(IVec2::ZERO).as_vec2();
+        let mut t_max = Vec2::select(
+            dir.cmpeq(Vec2::ZERO),
+            Vec2::splat(f32::INFINITY),
+            (next_boundary - start) / dir,
+        );
+        let t_delta = (Vec2::ONE / dir).abs();
+        let mut remaining = (last - current).abs();
+        let mut done = false;
+
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let index = current;
+            if remaining == IVec2::ZERO {
+                done = true;
+                return Some(index);
+            }
+            let diff = t_max.x - t_max.y;
+            let step_x = remaining.x > 0 && (remaining.y == 0 || diff < f32::EPSILON);
+            let step_y = remaining.y > 0 && (remaining.x == 0 || diff > -f32::EPSILON);
+            if step_x {
+                current.x += step.x;
+                t_max.x += t_delta.x;
+                remaining.x -= 1;
+            }
+            if step_y {
+                current.y += step.y;
+                t_max.y += t_delta.y;
+                remaining.y -= 1;
+            }
+            Some(index)
+        })
+    }
+
+    /// Convert a world position to the index of the metatile it's in.
+    ///
+    /// Metatile indices are relative to the camera's [`CameraOrigin`].
+    pub fn world_to_metatile_index(
+        &self,
+        cam_transform: &GlobalTransform,
+        world_pos: impl Point2d,
+    ) -> IVec2 {
+        let index = self.world_to_index(cam_transform, world_pos);
+        index.div_euclid(self.metatile_size.max(UVec2::ONE).as_ivec2())
+    }
+
+    /// Returns an iterator that yields the world center of every metatile
+    /// that overlaps the camera's virtual grid.
+    pub fn metatile_center_iter(
+        &self,
+        cam_transform: &GlobalTransform,
+    ) -> impl Iterator<Item = Vec2> + '_ {
+        let xy = self.view_center(cam_transform);
+        let size = self.metatile_size.max(UVec2::ONE).as_ivec2();
+        let half = self.cell_size_world() * size.as_vec2() / 2.0;
+        let (min, max) = self.index_range();
+        let (min, max) = (min.div_euclid(size), max.div_euclid(size));
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .map(move |i| self.index_to_local_pos(i * size) + half + xy)
+    }
+
+    /// Convert a world position to it's virtual tile position, measured in
+    /// tiles.
+    ///
+    /// A tile's "position" refers to the bottom left point of the tile, so
+    /// flooring the result gives the same index as [`TiledCamera::world_to_index`].
+    pub fn world_to_tile(&self, cam_transform: &GlobalTransform, world_pos: impl Point2d) -> Vec2 {
+        let local = self.world_to_local(cam_transform, world_pos);
+        let (min, _) = self.index_range();
+        let grid_min = -self.grid.world_size() / 2.0;
+        (local - grid_min) / self.cell_size_world() + min.as_vec2()
+    }
+
+    /// Convert a tile index to it's virtual tile position in world space.
+    ///
+    /// Tiles indices are relative to the camera's [`CameraOrigin`].
+    ///
+    /// A tile's "position" refers to the bottom left point of the tile.
+    pub fn index_to_tile_pos(&self, cam_transform: &GlobalTransform, pos: impl GridPoint) -> Vec2 {
+        let p = self.index_to_local_pos(pos.as_ivec2());
+        self.local_to_world(cam_transform, p)
+    }
+
+    /// Return the world center of the virtual tile at the given tile index.
+    ///
+    /// Tile indices are relative to the camera's [`CameraOrigin`].
+    pub fn index_to_tile_center(
+        &self,
+        cam_transform: &GlobalTransform,
+        index: impl GridPoint,
+    ) -> Vec2 {
+        let p = self.index_to_local_center(index.as_ivec2());
+        self.local_to_world(cam_transform, p)
+    }
+
+    /// Convert a batch of tile indices to their world centers, appending the
+    /// results to `out`.
+    ///
+    /// This is equivalent to calling [`TiledCamera::index_to_tile_center`]
+    /// for every index, but avoids redundant work for large batches.
+    pub fn indices_to_centers(
+        &self,
+        cam_transform: &GlobalTransform,
+        indices: &[IVec2],
+        out: &mut Vec<Vec2>,
+    ) {
+        let xy = self.view_center(cam_transform);
+        out.reserve(indices.len());
+        out.extend(indices.iter().map(|&i| self.index_to_local_center(i) + xy));
+    }
+
+    /// Adjust a sprite's world position so the sprite's pixels line up with
+    /// the camera's pixel grid, given the sprite's size in pixels and it's
+    /// [`Anchor`].
+    ///
+    /// This assumes the sprite is drawn at it's native pixel size, which for
+    /// [`WorldSpace::Units`] is `1 / pixels_per_tile.y` world units per pixel.
+    pub fn align_sprite_position(&self, world_pos: Vec2, sprite_px: UVec2, anchor: Anchor) -> Vec2 {
+        let pixel = self.pixel_size_world();
+        let size = sprite_px.as_vec2() * pixel;
+        let corner_offset = (anchor.as_vec() + 0.5) * size;
+        let corner = world_pos - corner_offset;
+        (corner / pixel).round() * pixel + corner_offset
+    }
+
+    /// Map a set of grid points to the world centers of their corresponding
+    /// tiles.
+    ///
+    /// Grid points are tile indices relative to the camera's [`CameraOrigin`].
+    pub fn grid_region_to_world<'a, I, P>(
+        &'a self,
+        cam_transform: &GlobalTransform,
+        points: I,
+    ) -> impl Iterator<Item = Vec2> + 'a
+    where
+        I: IntoIterator<Item = P>,
+        I::IntoIter: 'a,
+        P: GridPoint,
+    {
+        let xy = self.view_center(cam_transform);
+        points
+            .into_iter()
+            .map(move |p| self.index_to_local_center(p.as_ivec2()) + xy)
+    }
+
+    /// Snap a world position to the camera's pixel grid, using the camera's
+    /// [`SnapGranularity`].
+    pub fn snap_to_pixel(&self, world_pos: Vec2) -> Vec2 {
+        let pixel = self.pixel_size_world();
+        let step = match self.snap_granularity {
+            SnapGranularity::Pixel => pixel,
+            SnapGranularity::Pixels(n) => pixel * n.max(1) as f32,
+            SnapGranularity::Tile => self.cell_size_world(),
+        };
+        (world_pos / step).round() * step
+    }
+
+    /// Snap any world position (IE: an object being placed) to the same grid
+    /// the camera snaps to with [`PixelSnap`].
+    ///
+    /// This uses the camera's [`SnapGranularity`] and [`WorldSpace`], see
+    /// [`TiledCamera::snap_to_pixel`].
+    pub fn pixel_snap_world(&self, world_pos: Vec2) -> Vec2 {
+        self.snap_to_pixel(world_pos)
+    }
+
+    /// Snap a world position to the center of the virtual tile it's in.
+    ///
+    /// Positions outside the camera's grid snap to where the tile would be if
+    /// the grid extended that far.
+    pub fn snap_to_tile_center(&self, cam_transform: &GlobalTransform, world_pos: Vec2) -> Vec2 {
+        let index = self.world_to_index(cam_transform, world_pos);
+        self.index_to_tile_center(cam_transform, index)
+    }
+
+    /// Snap a world position to the nearest corner of the camera's virtual
+    /// tiles.
+    ///
+    /// Positions outside the camera's grid snap to where the tile would be if
+    /// the grid extended that far.
+    pub fn snap_to_tile_corner(&self, cam_transform: &GlobalTransform, world_pos: Vec2) -> Vec2 {
+        let cell = self.cell_size_world();
+        let grid_min = -self.grid.world_size() / 2.0;
+        let local = self.world_to_local(cam_transform, world_pos) - grid_min;
+        let local = (local / cell).round() * cell + grid_min;
+        self.local_to_world(cam_transform, local)
+    }
+
+    /// Change the camera's [`WorldSpace`].
+    pub fn set_world_space(&mut self, world_space: WorldSpace) {
+        self.world_space = world_space.into();
+        self.grid.world_space = world_space;
+    }
+
+    /// Get the camera's [`WorldSpace`].
+    pub fn world_space(&self) -> WorldSpace {
+        self.world_space.into()
+    }
+
+    /// The factor to scale world positions (including the camera's
+    /// translation) by when switching the camera to the other [`WorldSpace`],
+    /// so the view doesn't change.
+    ///
+    /// One world unit is `pixels_per_tile.y` pixels.
+    pub fn world_space_transition_scale(&self) -> Vec2 {
+        let pixels_per_unit = self.pixels_per_tile.y.max(1) as f32;
+        match self.world_space() {
+            WorldSpace::Units => Vec2::splat(pixels_per_unit),
+            WorldSpace::Pixels => Vec2::splat(1.0 / pixels_per_unit),
+        }
+    }
+
+    /// Change the camera's [`WorldSpace`], scaling `transform` so the camera
+    /// keeps showing the same part of the world.
+    ///
+    /// World content must be scaled by the same factor, see
+    /// [`TiledCamera::world_space_transition_scale`].
+    pub fn set_world_space_preserving_view(
+        &mut self,
+        world_space: WorldSpace,
+        transform: &mut Transform,
+    ) {
+        if world_space == self.world_space() {
+            return;
+        }
+        let scale = self.world_space_transition_scale();
+        let pos = transform.translation.truncate() * scale;
+        transform.translation = pos.extend(transform.translation.z);
+        self.set_world_space(world_space);
+    }
+
+    /// Convert a position from `source_space` to the camera's current [`WorldSpace`].
+    ///
+    /// This can be used to import positions that were authored for a different
+    /// world space. One world unit is `pixels_per_tile.y` pixels.
+    pub fn import_position(&self, pos: Vec2, source_space: WorldSpace) -> Vec2 {
+        self.convert_world_point(pos, source_space, self.world_space())
+    }
+
+    /// Convert a world position from one [`WorldSpace`] to another.
+    ///
+    /// One world unit is `pixels_per_tile.y` pixels on both axes, so a tile is
+    /// the same size in either space (see [`TiledCamera::cell_size_world`]).
+    pub fn convert_world_point(&self, p: Vec2, from: WorldSpace, to: WorldSpace) -> Vec2 {
+        let pixels_per_unit = self.pixels_per_tile.y.max(1) as f32;
+        match (from, to) {
+            (WorldSpace::Units, WorldSpace::Pixels) => p * pixels_per_unit,
+            (WorldSpace::Pixels, WorldSpace::Units) => p / pixels_per_unit,
+            _ => p,
+        }
+    }
+
+    /// Convert the translations of a set of transforms from one [`WorldSpace`]
+    /// to another, IE: to migrate existing entities after changing the
+    /// camera's world space.
+    ///
+    /// Z positions are left unchanged. See [`TiledCamera::convert_world_point`].
+    pub fn convert_translations<'a>(
+        &self,
+        transforms: impl IntoIterator<Item = &'a mut Transform>,
+        from: WorldSpace,
+        to: WorldSpace,
+    ) {
+        for transform in transforms {
+            let p = self.convert_world_point(transform.translation.truncate(), from, to);
+            transform.translation = p.extend(transform.translation.z);
+        }
+    }
+
+    /// The size of a single grid cell (IE: one tile) in world space.
+    ///
+    /// In [`WorldSpace::Units`] a tile is one unit tall, and it's width is
+    /// scaled to match the aspect ratio of `pixels_per_tile`. In
+    /// [`WorldSpace::Pixels`] a tile is `pixels_per_tile` units in size.
+    pub fn cell_size_world(&self) -> Vec2 {
+        let ppt = self.grid.pixels_per_tile.max(UVec2::ONE).as_vec2();
+        match self.world_space() {
+            WorldSpace::Units => Vec2::new(ppt.x / ppt.y, 1.0),
+            WorldSpace::Pixels => ppt,
+        }
+    }
+
+    /// The size of a single pixel in world space.
+    fn pixel_size_world(&self) -> Vec2 {
+        self.cell_size_world() / self.grid.pixels_per_tile.max(UVec2::ONE).as_vec2()
+    }
+
+    /// Get unit size or [`None`], depending on the camera's [`WorldSpace`].
+    ///
+    /// This can be used for sizing spawned sprites. If the camera's [`WorldSpace`]
+    /// is [`WorldSpace::Units`] then a unit sized sprite should be the size of
+    /// a tile.
+    /// Otherwise it should use the default sprite size, which is the pixel dimensions
+    /// of the sprite's texture.
+    pub fn unit_size(&self) -> Option<Vec2> {
+        match self.world_space() {
+            WorldSpace::Units => Some(self.cell_size_world()),
+            WorldSpace::Pixels => None,
+        }
+    }
+
+    /// How many texture pixels fit vertically in one world unit.
+    ///
+    /// In [`WorldSpace::Units`] this is `pixels_per_tile.y`, since a tile is
+    /// one unit tall. In [`WorldSpace::Pixels`] it's always 1.
+    pub fn pixels_per_unit(&self) -> f32 {
+        match self.world_space() {
+            WorldSpace::Units => self.grid.pixels_per_tile.max(UVec2::ONE).y as f32,
+            WorldSpace::Pixels => 1.0,
+        }
+    }
+
+    /// The [`Transform`] scale that makes a sprite with a `texture_px` sized
+    /// texture (and no custom size) cover exactly `tiles` tiles.
+    ///
+    /// Bevy draws sprites one world unit per texture pixel, so in
+    /// [`WorldSpace::Pixels`] a texture drawn at `pixels_per_tile` resolution
+    /// needs no scaling. In [`WorldSpace::Units`] the sprite is scaled down to
+    /// match the size of a tile instead, see [`TiledCamera::unit_size`].
+    pub fn sprite_scale_for_texture(&self, texture_px: UVec2, tiles: UVec2) -> Vec2 {
+        tiles.as_vec2() * self.cell_size_world() / texture_px.max(UVec2::ONE).as_vec2()
+    }
+
+    /// How much the camera view is scaled up, based on target resolution and window size.
+    ///
+    /// If [`TiledCamera::integer_scaling`] is disabled this is rounded down,
+    /// see [`TiledCamera::zoom_f32`]. The zoom is always measured in
+    /// physical pixels, even with [`HiDpiMode::Logical`].
+    pub fn zoom(&self) -> u32 {
+        self.zoom.max(1.0) as u32
+    }
+
+    /// How much the camera view is scaled up, including any fractional part
+    /// if [`TiledCamera::integer_scaling`] is disabled.
+    pub fn zoom_f32(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Force the camera to use a specific zoom, regardless of window size.
+    ///
+    /// If the zoomed viewport is larger than the window it's cropped to the
+    /// window, along with the camera's projection, so only the middle of the
+    /// view is shown.
+    /// Set to [`None`] to go back to computing zoom from the window size.
+    pub fn set_forced_zoom(&mut self, zoom: Option<u32>) {
+        self.forced_zoom = zoom;
+    }
+
+    /// The zoom set by [`TiledCamera::set_forced_zoom`], if any.
+    pub fn forced_zoom(&self) -> Option<u32> {
+        self.forced_zoom
+    }
+
+    /// Stop managing the camera's viewport and projection.
+    ///
+    /// The camera will be reset to a full window viewport with a default
+    /// projection until [`TiledCamera::resume`] is called.
+    pub fn suspend(&mut self) {
+        self.suspended = true;
+    }
+
+    /// Resume managing the camera's viewport and projection after a call to
+    /// [`TiledCamera::suspend`].
+    pub fn resume(&mut self) {
+        self.suspended = false;
+    }
+
+    /// Whether the camera is currently suspended. See [`TiledCamera::suspend`].
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    // MIT License
+    // Copyright (c) 2021 Aevyrie
+    // https://github.com/aevyrie/bevy_mod_raycast
+    /// Convert a screen position (IE: The mouse cursor position) to it's corresponding world position.
+    ///
+    /// Screen positions are in physical pixels, counted from the bottom left
+    /// of the window. Returns [`None`] if the screen position is outside the
+    /// camera's viewport, IE: in a letterbox bar.
+    pub fn screen_to_world(
+        &self,
+        screen_pos: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let projection = camera.projection_matrix();
+        #[cfg(debug_assertions)]
+        self.verify_round_trip(screen_pos, projection, camera_transform);
+        self.screen_to_world_proj(screen_pos, projection, camera_transform)
+    }
+
+    fn screen_to_world_proj(
+        &self,
+        screen_pos: Vec2,
+        projection: Mat4,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        if !self.screen_in_viewport(screen_pos) {
+            return None;
+        }
+        let screen_size = self.vp_size.as_vec2();
+        let screen_pos = (screen_pos - self.viewport_screen_min()).round();
+
+        let view = camera_transform.compute_matrix();
+
+        // 2D Normalized device coordinate cursor position from (-1, -1) to (1, 1)
+        let cursor_ndc = (screen_pos / screen_size) * 2.0 - Vec2::from([1.0, 1.0]);
+        let ndc_to_world: Mat4 = view * projection.inverse();
+        let world_to_ndc = projection * view;
 
         // Calculate the camera's near plane using the projection matrix
         let projection = projection.to_cols_array_2d();
         let camera_near = (2.0 * projection[3][2]) / (2.0 * projection[2][2] - 2.0);
 
-        // Compute the cursor position at the near plane. The bevy camera looks at -Z.
-        let ndc_near = world_to_ndc.transform_point3(-Vec3::Z * camera_near).z;
-        let cursor_pos_near = ndc_to_world.transform_point3(cursor_ndc.extend(ndc_near));
-        let tile_size = self.grid.tile_size_world();
-        let cursor_pos_near = cursor_pos_near.truncate() * tile_size;
-        // Former viewport issue - had to flip y. Was fixed in 0.9 release
-        //cursor_pos_near.y = -cursor_pos_near.y;
-        Some(cursor_pos_near)
+        // Compute the cursor position at the near plane. The bevy camera looks at -Z.
+        let ndc_near = world_to_ndc.transform_point3(-Vec3::Z * camera_near).z;
+        let cursor_pos_near = ndc_to_world.transform_point3(cursor_ndc.extend(ndc_near));
+        let cursor_pos_near = cursor_pos_near.truncate();
+        // Former viewport issue - had to flip y. Was fixed in 0.9 release
+        //cursor_pos_near.y = -cursor_pos_near.y;
+        Some(cursor_pos_near)
+    }
+
+    /// Convert the position from a [`CursorMoved`] event to it's corresponding
+    /// world position.
+    ///
+    /// Cursor positions are in logical pixels with the origin at the top left
+    /// of the window, this takes care of flipping and scaling them to the
+    /// bottom left origin physical screen position [`TiledCamera::screen_to_world`] expects.
+    pub fn cursor_event_to_world(
+        &self,
+        event: &CursorMoved,
+        window: &Window,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let screen_pos = cursor_to_screen(event.position, window);
+        self.screen_to_world(screen_pos, camera, camera_transform)
+    }
+
+    /// Convert the window's current cursor position to it's corresponding
+    /// world position.
+    ///
+    /// Like [`TiledCamera::cursor_event_to_world`], but for systems that poll
+    /// the cursor every frame instead of reading [`CursorMoved`] events.
+    /// Returns `None` if the cursor isn't in the window or the viewport.
+    pub fn cursor_to_world(
+        &self,
+        window: &Window,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let screen_pos = cursor_to_screen(window.cursor_position()?, window);
+        self.screen_to_world(screen_pos, camera, camera_transform)
+    }
+
+    /// Convert a position in normalized device coordinates, from `[-1,-1]` at
+    /// the bottom left of the viewport to `[1,1]` at the top right, to it's
+    /// corresponding world position.
+    ///
+    /// This is useful for virtual cursors (IE: controlled by a gamepad) that
+    /// don't live in window pixels.
+    pub fn virtual_cursor_to_world(
+        &self,
+        ndc: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Vec2 {
+        ndc_to_world_proj(ndc, camera.projection_matrix(), camera_transform)
+    }
+
+    /// Convert a screen position (IE: The mouse cursor position) to the
+    /// virtual tile index under it.
+    ///
+    /// Returns [`None`] if the screen position is outside the camera's viewport.
+    /// Tile indices are relative to the camera's [`CameraOrigin`], the same
+    /// as [`TiledCamera::world_to_index`].
+    pub fn screen_to_index(
+        &self,
+        screen_pos: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<IVec2> {
+        self.screen_to_index_proj(screen_pos, camera.projection_matrix(), camera_transform)
+    }
+
+    fn screen_to_index_proj(
+        &self,
+        screen_pos: Vec2,
+        projection: Mat4,
+        camera_transform: &GlobalTransform,
+    ) -> Option<IVec2> {
+        let world = self.screen_to_world_proj(screen_pos, projection, camera_transform)?;
+        Some(self.world_to_index(camera_transform, world))
+    }
+
+    /// Pick the tile under a screen position (IE: The mouse cursor position).
+    ///
+    /// Returns [`None`] if the screen position is outside the camera's viewport.
+    pub fn pick(
+        &self,
+        screen_pos: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<TiledPick> {
+        self.pick_proj(screen_pos, camera.projection_matrix(), camera_transform)
+    }
+
+    fn pick_proj(
+        &self,
+        screen_pos: Vec2,
+        projection: Mat4,
+        camera_transform: &GlobalTransform,
+    ) -> Option<TiledPick> {
+        let world = self.screen_to_world_proj(screen_pos, projection, camera_transform)?;
+        let tile_index = self.world_to_index(camera_transform, world);
+        let xy = self.view_center(camera_transform);
+        Some(TiledPick {
+            world,
+            tile_index,
+            tile_center: self.index_to_local_center(tile_index) + xy,
+            in_bounds: self.index_in_bounds(tile_index),
+        })
+    }
+
+    /// Converts a world position to a screen position (0..resolution)
+    ///
+    /// Screen positions are in physical pixels, counted from the bottom left
+    /// of the window.
+    pub fn world_to_screen(
+        &self,
+        world_pos: impl Point2d,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let projection = camera.projection_matrix();
+        let screen_pos = self.world_to_screen_proj(world_pos, projection, camera_transform);
+        #[cfg(debug_assertions)]
+        if let Some(screen_pos) = screen_pos {
+            self.verify_round_trip(screen_pos, projection, camera_transform);
+        }
+        screen_pos
+    }
+
+    /// Converts a world position to a screen position, clamped to the edges
+    /// of the camera's viewport.
+    ///
+    /// The returned flag is true if the position was outside the viewport and
+    /// had to be clamped. This is useful for drawing markers at the edge of
+    /// the screen for off-screen objects.
+    pub fn world_to_screen_clamped(
+        &self,
+        world_pos: impl Point2d,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<(Vec2, bool)> {
+        let screen_pos = self.world_to_screen(world_pos, camera, camera_transform)?;
+        Some(self.clamp_to_viewport(screen_pos))
+    }
+
+    /// Converts a virtual tile index to the screen position of the tile's
+    /// center, IE: for anchoring UI to a tile.
+    ///
+    /// Like [`TiledCamera::world_to_screen`] the result is in physical pixels
+    /// counted from the bottom left of the window, use
+    /// [`TiledCamera::screen_to_ui_position`] to position UI nodes.
+    ///
+    /// Returns [`None`] if the tile's center is outside the camera's viewport.
+    /// Tile indices are relative to the camera's [`CameraOrigin`], the same
+    /// as [`TiledCamera::index_to_tile_center`].
+    pub fn index_to_screen(
+        &self,
+        index: impl GridPoint,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        self.index_to_screen_proj(index, camera.projection_matrix(), camera_transform)
+    }
+
+    fn index_to_screen_proj(
+        &self,
+        index: impl GridPoint,
+        projection: Mat4,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let world = self.index_to_tile_center(camera_transform, index);
+        let screen_pos = self.world_to_screen_proj(world, projection, camera_transform)?;
+        self.screen_in_viewport(screen_pos).then_some(screen_pos)
+    }
+
+    /// Convert a screen position to a UI position, IE: logical pixels counted
+    /// from the top left of the window, using the window size and scale
+    /// factor from the last viewport update.
+    ///
+    /// This doesn't account for bevy's `UiScale`.
+    pub fn screen_to_ui_position(&self, screen_pos: Vec2) -> Vec2 {
+        let pos = Vec2::new(screen_pos.x, self.win_size.y as f32 - screen_pos.y);
+        pos / self.scale_factor.max(f32::EPSILON)
+    }
+
+    fn clamp_to_viewport(&self, screen_pos: Vec2) -> (Vec2, bool) {
+        let min = self.viewport_screen_min();
+        let max = min + self.vp_size.as_vec2();
+        let clamped = screen_pos.clamp(min, max);
+        (clamped, clamped != screen_pos)
+    }
+
+    fn world_to_screen_proj(
+        &self,
+        world_pos: impl Point2d,
+        projection: Mat4,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let window_size = self.vp_size.as_vec2();
+        let ndc_space_coords = world_to_ndc_proj(world_pos.as_vec2(), projection, camera_transform);
+
+        // NDC z-values outside of 0 < z < 1 are outside the camera frustum and are thus not in screen space
+        if ndc_space_coords.z < 0.0 || ndc_space_coords.z > 1.0 {
+            return None;
+        }
+
+        // Once in NDC space, we can discard the z element and rescale x/y to fit the screen
+        let screen_space_coords = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0 * window_size;
+        if !screen_space_coords.is_nan() {
+            Some((screen_space_coords + self.viewport_screen_min()).round())
+        } else {
+            None
+        }
+    }
+
+    /// If `verify_conversions` is set, check that `screen_pos` survives a
+    /// round trip through world space, logging a warning if it doesn't.
+    ///
+    /// Returns false if the round trip was off by more than a pixel.
+    #[cfg(debug_assertions)]
+    fn verify_round_trip(
+        &self,
+        screen_pos: Vec2,
+        projection: Mat4,
+        camera_transform: &GlobalTransform,
+    ) -> bool {
+        if !self.verify_conversions || !self.screen_in_viewport(screen_pos) {
+            return true;
+        }
+        let round_trip = self
+            .screen_to_world_proj(screen_pos, projection, camera_transform)
+            .and_then(|world| self.world_to_screen_proj(world, projection, camera_transform));
+        match round_trip {
+            Some(p) if p.distance(screen_pos) <= 1.0 => true,
+            _ => {
+                warn!(
+                    "TiledCamera screen/world conversion mismatch: {} round tripped to {:?}",
+                    screen_pos, round_trip
+                );
+                false
+            }
+        }
+    }
+
+    /// Retrieve the camera's [`WorldGrid`].
+    pub fn world_grid(&self) -> &WorldGrid {
+        &self.grid
+    }
+}
+
+/// Determines the step size used when snapping positions to the camera's
+/// pixel grid.
+///
+/// One pixel is one world unit in [`WorldSpace::Pixels`], and
+/// `1 / pixels_per_tile.y` world units in [`WorldSpace::Units`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SnapGranularity {
+    /// Snap to whole pixels.
+    #[default]
+    Pixel,
+    /// Snap to whole tiles.
+    Tile,
+    /// Snap to multiples of the given number of pixels.
+    Pixels(u32),
+}
+
+/// Sent once for each [`TiledCamera`] after it's viewport is first set up,
+/// IE: when [`TiledCamera::is_initialized`] first becomes true.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiledCameraInitialized {
+    /// The camera entity.
+    pub entity: Entity,
+    /// The camera's initial viewport size.
+    pub vp_size: UVec2,
+    /// The camera's initial zoom.
+    pub zoom: u32,
+}
+
+/// Sent whenever a [`TiledCamera`]'s zoom or viewport changes, including
+/// when it's first initialized.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiledCameraResized {
+    /// The camera entity.
+    pub entity: Entity,
+    /// The camera's new zoom.
+    pub zoom: u32,
+    /// The camera's new viewport size.
+    pub vp_size: UVec2,
+    /// The camera's new viewport position.
+    pub vp_pos: UVec2,
+}
+
+/// Sent when a [`TiledCamera`]'s integer zoom changes, IE: from 3x to 4x
+/// after the window is resized.
+///
+/// Unlike [`TiledCameraResized`] this isn't sent for window size changes
+/// that don't change the zoom, or when the camera is first initialized.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoomChanged {
+    /// The camera entity.
+    pub entity: Entity,
+    /// The camera's previous zoom.
+    pub old: u32,
+    /// The camera's new zoom.
+    pub new: u32,
+}
+
+/// Add this component to a [`TiledCamera`] entity to have it follow another
+/// entity.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct TiledCameraFollow {
+    /// The entity to follow.
+    pub target: Entity,
+    /// If set, the camera moves `follow_lerp * delta_seconds` of the way
+    /// towards the target each frame instead of snapping to it.
+    pub follow_lerp: Option<f32>,
+    /// If set, the camera will never be more than this many tiles away from
+    /// the target on either axis, even while smoothing.
+    pub max_lag_tiles: Option<f32>,
+    /// If set, the camera stops following the target at the edges of these
+    /// world space bounds, so it's view never leaves them.
+    pub bounds: Option<Rect>,
+}
+
+impl TiledCameraFollow {
+    /// Follow `target`, snapping to it's position every frame.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            follow_lerp: None,
+            max_lag_tiles: None,
+            bounds: None,
+        }
+    }
+}
+
+/// How much of a world space rect is inside a camera's view.
+///
+/// See [`TiledCamera::rect_visibility`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RectVisibility {
+    /// The rect is entirely inside the camera's view.
+    Fully,
+    /// Part of the rect is inside the camera's view.
+    Partial,
+    /// The rect is entirely outside the camera's view.
+    None,
+}
+
+/// Add this component to a [`TiledCamera`] entity to snap it's rendered
+/// position to the camera's pixel grid.
+///
+/// Snapping is only applied to the camera's [`GlobalTransform`] after
+/// transform propagation, so the camera's [`Transform`] keeps it's unsnapped
+/// position. See [`TiledCamera::logical_position`].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct PixelSnap;
+
+/// The result of [`TiledCamera::pick`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TiledPick {
+    /// The picked world position.
+    pub world: Vec2,
+    /// The index of the picked tile, relative to the camera's
+    /// [`CameraOrigin`].
+    pub tile_index: IVec2,
+    /// The world center of the picked tile.
+    pub tile_center: Vec2,
+    /// Whether the picked tile is inside the camera's grid.
+    pub in_bounds: bool,
+}
+
+/// Determines how the camera fills the window when the window size is not an
+/// exact multiple of the target resolution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ResponsiveMode {
+    /// Exactly `tile_count` tiles are shown. Any leftover window space is
+    /// left empty, "letterboxing" the viewport.
+    #[default]
+    Fixed,
+    /// At least `tile_count` tiles are shown. Zoom is chosen so `tile_count`
+    /// tiles fit, then more tiles are revealed to fill the rest of the window.
+    ///
+    /// The camera's grid is expanded to cover the visible area, so tiles at the
+    /// edge of the window may only be partially visible.
+    MinTiles,
+    /// The vertical tile count is fixed and `tile_count.x` is changed to
+    /// match the aspect ratio of the window, rounded down to whole tiles.
+    ///
+    /// Note this changes the camera's `tile_count` whenever the viewport is
+    /// updated.
+    FlexibleWidth,
+}
+
+/// Determines which axis of the camera's target resolution is guaranteed to be
+/// fully visible.
+///
+/// Zoom is always rounded down to an integer, so the chosen axis will be
+/// letterboxed rather than cropped. The other axis may be cropped if the
+/// window's aspect ratio doesn't match the target resolution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ScaleAxis {
+    /// Scale so all vertical tiles are visible.
+    Vertical,
+    /// Scale so all horizontal tiles are visible.
+    Horizontal,
+    /// Scale so all tiles on both axes are visible, using whichever axis has
+    /// the least room to spare.
+    #[default]
+    Auto,
+}
+
+/// Determines what happens when the window is too small to fit the camera's
+/// target resolution at it's minimum zoom.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum MinZoomPolicy {
+    /// The edges of the view are clipped, IE: the viewport is cropped to the
+    /// window, showing only the middle of the camera's view.
+    ///
+    /// Any axis that doesn't fit is cropped evenly on both sides, so the
+    /// center of the view stays in the center of the window. Axes that do fit
+    /// are letterboxed as usual.
+    #[default]
+    Clip,
+    /// Fewer tiles are shown so the viewport fits inside the window at the
+    /// minimum zoom.
+    ReduceTiles,
+}
+
+/// Determines which tile of a camera's virtual grid is at index `[0,0]`.
+///
+/// Only tile indices are affected, the grid itself is always centered on the
+/// camera's view.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CameraOrigin {
+    /// Index `[0,0]` is the center tile. With an even tile count this is the
+    /// tile up and to the right of the center.
+    #[default]
+    Center,
+    /// Index `[0,0]` is the bottom left tile, so every index in the camera's
+    /// view is positive.
+    BottomLeft,
+}
+
+/// Determines how the window's scale factor affects the camera's zoom.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum HiDpiMode {
+    /// The zoom is chosen to fit the window's physical size, ignoring it's
+    /// scale factor. On a 2x display the camera will usually be zoomed twice
+    /// as far.
+    #[default]
+    Physical,
+    /// The zoom is chosen to fit the window's logical size, then multiplied
+    /// by the window's scale factor.
+    ///
+    /// The game will appear the same size on displays with different scale
+    /// factors, and each game pixel is always a whole number of logical
+    /// pixels. With fractional scale factors the zoom in physical pixels may
+    /// not be a whole number.
+    Logical,
+}
+
+/// Space in physical pixels to keep clear around each edge of a camera's
+/// window region. See [`TiledCamera::viewport_margins`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct ViewportMargins {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+impl ViewportMargins {
+    pub fn new(left: u32, right: u32, top: u32, bottom: u32) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// The same margin on every edge.
+    pub fn all(margin: u32) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+}
+
+/// Determines where the viewport is placed when the window space around it
+/// can't be split evenly between the letterbox bars on each side.
+///
+/// Window pixels are counted from the top left of the window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CenterBias {
+    /// The odd pixel goes to the right and bottom bars.
+    #[default]
+    FloorLow,
+    /// The odd pixel goes to the left and top bars.
+    Round,
+    /// The odd pixel goes to the left bar and the bottom bar, so it's split
+    /// between opposite sides across the two axes.
+    SplitEven,
+}
+
+impl CenterBias {
+    /// Viewport position for the given amount of leftover window space.
+    fn viewport_pos(&self, leftover: Vec2) -> Vec2 {
+        let half = leftover / 2.0;
+        match self {
+            CenterBias::FloorLow => half.floor(),
+            CenterBias::Round => half.ceil(),
+            CenterBias::SplitEven => Vec2::new(half.x.ceil(), half.y.floor()),
+        }
+    }
+}
+
+impl Default for TiledCamera {
+    fn default() -> Self {
+        let pixels_per_tile = UVec2::new(8, 8);
+        let tile_count = UVec2::new(80, 35);
+        Self {
+            pixels_per_tile,
+            tile_count,
+            metatile_size: UVec2::ONE,
+            responsive_mode: ResponsiveMode::default(),
+            center_bias: CenterBias::default(),
+            camera_anchor: Vec2::splat(0.5),
+            origin: CameraOrigin::default(),
+            scale_axis: ScaleAxis::default(),
+            window_region: Rect::new(0.0, 0.0, 1.0, 1.0),
+            viewport_margins: ViewportMargins::default(),
+            bounds: None,
+            target_resolution_override: None,
+            design_resolution: None,
+            letterbox_color: None,
+            render_scale: 1.0,
+            snap_granularity: SnapGranularity::default(),
+            min_zoom: 1,
+            integer_scaling: true,
+            min_zoom_policy: MinZoomPolicy::default(),
+            hidpi_mode: HiDpiMode::default(),
+            render_offset: Vec2::ZERO,
+            integer_unit_position: false,
+            verify_conversions: false,
+            world_space: CameraWorldSpace::Units,
+            forced_zoom: None,
+            suspended: false,
+            fixed_viewport: false,
+            defer_until_window: false,
+            depth_range: (-1000.0, 1000.0),
+            scaled_output: None,
+            warned_invalid: false,
+            initialized: false,
+            logical_pos: Vec2::ZERO,
+            grid: WorldGrid::unit_grid(tile_count, pixels_per_tile),
+            zoom: 1.0,
+            vp_size: UVec2::ONE,
+            vp_pos: UVec2::ZERO,
+            win_size: UVec2::ONE,
+            scale_factor: 1.0,
+            ortho_size: 0.0,
+        }
+    }
+}
+
+fn update_viewports(
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    mut resize_events: EventReader<WindowResized>,
+    mut initialized_events: EventWriter<TiledCameraInitialized>,
+    mut resized_events: EventWriter<TiledCameraResized>,
+    mut zoom_events: EventWriter<ZoomChanged>,
+    mut q_cam: Query<(
+        Entity,
+        &mut OrthographicProjection,
+        &mut Camera,
+        &mut TiledCamera,
+    )>,
+) {
+    let primary_window = primary_window.get_single().ok();
+
+    // We need to dynamically resize the camera's viewports whenever the window
+    // size changes. A resize_event is sent when the window is first created,
+    // allowing us to reuse this system for initial setup.
+    let resized: Vec<Entity> = resize_events.read().map(|e| e.window).collect();
+
+    for (entity, mut proj, mut cam, mut tiled_cam) in q_cam.iter_mut() {
+        if tiled_cam.suspended {
+            if tiled_cam.is_changed() {
+                cam.viewport = None;
+                proj.scaling_mode = OrthographicProjection::default().scaling_mode;
+                proj.viewport_origin = OrthographicProjection::default().viewport_origin;
+            }
+            continue;
+        }
+        let wres = if tiled_cam.fixed_viewport && tiled_cam.initialized {
+            if !tiled_cam.is_changed() {
+                continue;
+            }
+            tiled_cam.win_size
+        } else {
+            // Cameras rendering to a window that doesn't exist are skipped.
+            let RenderTarget::Window(window_ref) = tiled_cam.output(&cam).0 else {
+                continue;
+            };
+            let Some(window_entity) = window_ref.normalize(primary_window).map(|w| w.entity())
+            else {
+                continue;
+            };
+            let Ok(window) = windows.get(window_entity) else {
+                continue;
+            };
+            if !resized.contains(&window_entity) && !tiled_cam.is_changed() {
+                continue;
+            }
+            // A minimized window has no room for any viewport.
+            if window.physical_width() == 0 || window.physical_height() == 0 {
+                continue;
+            }
+            if tiled_cam.scale_factor != window.scale_factor() {
+                tiled_cam.scale_factor = window.scale_factor();
+            }
+            UVec2::new(window.physical_width(), window.physical_height())
+        };
+
+        let was_initialized = tiled_cam.initialized;
+        let before = (tiled_cam.zoom, tiled_cam.vp_size, tiled_cam.vp_pos);
+        let old_zoom = tiled_cam.zoom();
+        update_viewport(&mut tiled_cam, wres, &mut proj, &mut cam);
+        if !was_initialized {
+            initialized_events.send(TiledCameraInitialized {
+                entity,
+                vp_size: tiled_cam.vp_size,
+                zoom: tiled_cam.zoom(),
+            });
+        }
+        if before != (tiled_cam.zoom, tiled_cam.vp_size, tiled_cam.vp_pos) {
+            resized_events.send(TiledCameraResized {
+                entity,
+                zoom: tiled_cam.zoom(),
+                vp_size: tiled_cam.vp_size,
+                vp_pos: tiled_cam.vp_pos,
+            });
+        }
+        if was_initialized && old_zoom != tiled_cam.zoom() {
+            zoom_events.send(ZoomChanged {
+                entity,
+                old: old_zoom,
+                new: tiled_cam.zoom(),
+            });
+        }
+    }
+}
+
+/// Marks an entity spawned to draw a [`TiledCamera`]'s letterbox bars.
+#[derive(Component)]
+struct Letterbox {
+    owner: Entity,
+}
+
+/// Which of the owning camera's letterbox bars a sprite draws, in the same
+/// order as [`TiledCamera::letterbox_rects`].
+#[derive(Component)]
+struct LetterboxBar(usize);
+
+fn update_letterbox(
+    mut commands: Commands,
+    q_cam: Query<(Entity, Ref<TiledCamera>, &Camera)>,
+    mut q_letterbox_cam: Query<(Entity, &Letterbox, &mut Camera), Without<TiledCamera>>,
+    mut q_bars: Query<(
+        Entity,
+        &Letterbox,
+        &LetterboxBar,
+        &mut Sprite,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    let active = |owner: Entity| {
+        q_cam.get(owner).is_ok_and(|(_, tiled_cam, _)| {
+            tiled_cam.letterbox_color.is_some() && tiled_cam.initialized && !tiled_cam.suspended
+        })
+    };
+
+    // Clean up after cameras that were removed or no longer have bars.
+    for (entity, letterbox, _) in q_letterbox_cam.iter() {
+        if !active(letterbox.owner) {
+            commands.entity(entity).despawn();
+        }
+    }
+    for (entity, letterbox, ..) in q_bars.iter() {
+        if !active(letterbox.owner) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (owner, tiled_cam, cam) in q_cam.iter() {
+        if !tiled_cam.is_changed() || !active(owner) {
+            continue;
+        }
+        let Some(color) = tiled_cam.letterbox_color else {
+            continue;
+        };
+
+        // The tiled camera clears the whole window, then the letterbox camera
+        // draws the bars over top.
+        let (target, order) = tiled_cam.output(cam);
+        let order = LETTERBOX_ORDER + order;
+        let existing = q_letterbox_cam
+            .iter_mut()
+            .find(|(_, letterbox, _)| letterbox.owner == owner);
+        if let Some((_, _, mut letterbox_cam)) = existing {
+            letterbox_cam.order = order;
+            letterbox_cam.target = target.clone();
+        } else {
+            commands.spawn((
+                Camera2dBundle {
+                    camera: Camera {
+                        order,
+                        target: target.clone(),
+                        clear_color: ClearColorConfig::None,
+                        ..default()
+                    },
+                    ..default()
+                },
+                RenderLayers::layer(LETTERBOX_LAYER),
+                Letterbox { owner },
+            ));
+        }
+
+        // The letterbox camera's projection maps one world unit to one
+        // logical pixel, centered on the window.
+        let scale = match tiled_cam.scaled_output {
+            Some(_) => tiled_cam.scale_factor,
+            None => cam.target_scaling_factor().unwrap_or(1.0),
+        };
+        let wres = tiled_cam.win_size.as_vec2();
+        let bar = |rect: Rect| {
+            let center = (rect.center() - wres / 2.0) * Vec2::new(1.0, -1.0) / scale;
+            let visibility = if rect.is_empty() {
+                Visibility::Hidden
+            } else {
+                Visibility::Inherited
+            };
+            (rect.size() / scale, center.extend(0.0), visibility)
+        };
+        let rects = tiled_cam.letterbox_bars(tiled_cam.win_size);
+        let mut spawned = [false; 4];
+        for (_, letterbox, side, mut sprite, mut transform, mut visibility) in q_bars.iter_mut() {
+            if letterbox.owner != owner {
+                continue;
+            }
+            let (size, center, vis) = bar(rects[side.0]);
+            sprite.color = color;
+            sprite.custom_size = Some(size);
+            transform.translation = center;
+            *visibility = vis;
+            spawned[side.0] = true;
+        }
+        for (side, rect) in rects.into_iter().enumerate() {
+            if spawned[side] {
+                continue;
+            }
+            let (size, center, visibility) = bar(rect);
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(center),
+                    visibility,
+                    ..default()
+                },
+                RenderLayers::layer(LETTERBOX_LAYER),
+                Letterbox { owner },
+                LetterboxBar(side),
+            ));
+        }
+    }
+}
+
+/// Marks the camera and sprite spawned to upscale a [`TiledCamera`]'s image
+/// to it's viewport, see [`TiledCamera::render_scale`].
+#[derive(Component)]
+struct RenderScale {
+    owner: Entity,
+}
+
+fn update_render_scale(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut q_cam: Query<(Entity, &mut TiledCamera, &mut Camera)>,
+    mut q_upscale_cam: Query<(Entity, &RenderScale, &mut Camera), Without<TiledCamera>>,
+    q_sprites: Query<(Entity, &RenderScale), Without<Camera>>,
+) {
+    let active = |tiled_cam: &TiledCamera| {
+        tiled_cam.render_scale < 1.0 && tiled_cam.initialized && !tiled_cam.suspended
+    };
+
+    // Clean up after cameras that were removed.
+    for (entity, upscale, _) in q_upscale_cam.iter() {
+        if q_cam.get(upscale.owner).is_err() {
+            commands.entity(entity).despawn();
+        }
+    }
+    for (entity, upscale) in q_sprites.iter() {
+        if q_cam.get(upscale.owner).is_err() {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (owner, mut tiled_cam, mut cam) in q_cam.iter_mut() {
+        let changed = tiled_cam.is_changed();
+        if !active(&tiled_cam) {
+            // Render straight to the window again.
+            let Some((target, order)) = tiled_cam.bypass_change_detection().scaled_output.take()
+            else {
+                continue;
+            };
+            if let RenderTarget::Image(image) = &cam.target {
+                images.remove(image);
+            }
+            cam.target = target;
+            cam.order = order;
+            cam.viewport = (!tiled_cam.suspended).then(|| Viewport {
+                physical_position: tiled_cam.vp_pos,
+                physical_size: tiled_cam.vp_size,
+                ..default()
+            });
+            for (entity, upscale, _) in q_upscale_cam.iter() {
+                if upscale.owner == owner {
+                    commands.entity(entity).despawn();
+                }
+            }
+            for (entity, upscale) in q_sprites.iter() {
+                if upscale.owner == owner {
+                    commands.entity(entity).despawn();
+                }
+            }
+            continue;
+        }
+
+        let size = tiled_cam.render_image_size();
+        let size = Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        };
+        // The camera fills the whole image, the upscale camera draws it to the
+        // camera's viewport.
+        let viewport = Viewport {
+            physical_position: tiled_cam.vp_pos,
+            physical_size: tiled_cam.vp_size,
+            ..default()
+        };
+        if tiled_cam.scaled_output.is_none() {
+            if !matches!(cam.target, RenderTarget::Window(_)) {
+                continue;
+            }
+            let format = if cam.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            };
+            let mut image = Image::new_fill(
+                size,
+                TextureDimension::D2,
+                &vec![0; format.pixel_size()],
+                format,
+                RenderAssetUsages::default(),
+            );
+            image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT;
+            image.sampler = ImageSampler::nearest();
+            let image = images.add(image);
+
+            // Each owner's sprite is placed apart from the others so every
+            // upscale camera only sees it's own image.
+            let x = owner.index() as f32 * 2.0;
+            let mut upscale_cam = Camera2dBundle {
+                camera: Camera {
+                    order: cam.order,
+                    target: cam.target.clone(),
+                    viewport: Some(viewport),
+                    clear_color: cam.clear_color.clone(),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x, 0.0, 1.0),
+                ..default()
+            };
+            upscale_cam.projection.scaling_mode = ScalingMode::Fixed {
+                width: 1.0,
+                height: 1.0,
+            };
+            commands.spawn((
+                upscale_cam,
+                RenderLayers::layer(RENDER_SCALE_LAYER),
+                RenderScale { owner },
+            ));
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::ONE),
+                        ..default()
+                    },
+                    texture: image.clone(),
+                    transform: Transform::from_xyz(x, 0.0, 0.0),
+                    ..default()
+                },
+                RenderLayers::layer(RENDER_SCALE_LAYER),
+                RenderScale { owner },
+            ));
+
+            tiled_cam.bypass_change_detection().scaled_output =
+                Some((cam.target.clone(), cam.order));
+            cam.order += RENDER_SCALE_ORDER;
+            cam.target = RenderTarget::Image(image);
+            cam.viewport = None;
+        } else if changed {
+            let image = match &cam.target {
+                RenderTarget::Image(image) => images.get_mut(image),
+                _ => None,
+            };
+            if let Some(image) = image.filter(|image| image.texture_descriptor.size != size) {
+                image.resize(size);
+            }
+            cam.viewport = None;
+            let (target, order) = tiled_cam.output(&cam);
+            for (_, upscale, mut upscale_cam) in q_upscale_cam.iter_mut() {
+                if upscale.owner != owner {
+                    continue;
+                }
+                upscale_cam.order = order;
+                upscale_cam.target = target.clone();
+                upscale_cam.clear_color = cam.clear_color.clone();
+                upscale_cam.viewport = Some(viewport.clone());
+            }
+        }
+    }
+}
+
+fn follow_target(
+    time: Res<Time>,
+    mut q_cam: Query<(&TiledCamera, &TiledCameraFollow, &mut Transform)>,
+    q_target: Query<&GlobalTransform>,
+) {
+    for (tiled_cam, follow, mut transform) in q_cam.iter_mut() {
+        let Ok(target) = q_target.get(follow.target) else {
+            continue;
+        };
+        let target = target.translation().truncate();
+        let pos = transform.translation.truncate();
+        let mut next = match follow.follow_lerp {
+            Some(lerp) => pos.lerp(target, (lerp * time.delta_seconds()).clamp(0.0, 1.0)),
+            None => target,
+        };
+        if let Some(max_lag) = follow.max_lag_tiles {
+            let max_lag = max_lag * tiled_cam.cell_size_world();
+            next = target + (next - target).clamp(-max_lag, max_lag);
+        }
+        if let Some(bounds) = follow.bounds {
+            next = tiled_cam.clamp_translation_to_bounds(next, bounds);
+        }
+        if next != pos {
+            transform.translation = next.extend(transform.translation.z);
+        }
+    }
+}
+
+fn clamp_to_bounds(mut q_cam: Query<(&TiledCamera, &mut Transform)>) {
+    for (tiled_cam, mut transform) in q_cam.iter_mut() {
+        let Some(bounds) = tiled_cam.bounds else {
+            continue;
+        };
+        let pos = transform.translation.truncate();
+        let clamped = tiled_cam.clamp_translation_to_bounds(pos, bounds);
+        if clamped != pos {
+            transform.translation = clamped.extend(transform.translation.z);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_render_transform(
+    mut q_cam: Query<(
+        &mut TiledCamera,
+        &Transform,
+        &mut GlobalTransform,
+        Option<&Parent>,
+        Has<PixelSnap>,
+    )>,
+    q_parent: Query<&GlobalTransform, Without<TiledCamera>>,
+) {
+    for (mut tiled_cam, transform, mut global, parent, snap) in q_cam.iter_mut() {
+        // Recompute the logical transform, since `global` may still hold
+        // last frame's offset if the transform didn't change.
+        let logical = match parent.and_then(|p| q_parent.get(p.get()).ok()) {
+            Some(parent) => parent.mul_transform(*transform),
+            None => GlobalTransform::from(*transform),
+        };
+        let logical_pos = logical.translation().truncate();
+        if tiled_cam.logical_pos != logical_pos {
+            tiled_cam.bypass_change_detection().logical_pos = logical_pos;
+        }
+
+        let mut pos = logical_pos;
+        if tiled_cam.integer_unit_position && tiled_cam.world_space() == WorldSpace::Units {
+            pos = pos.round();
+        }
+        pos += tiled_cam.render_offset;
+        if snap {
+            pos = tiled_cam.snap_to_pixel(pos);
+        }
+        let offset = (pos - logical_pos).extend(0.0);
+        let rendered = GlobalTransform::from_translation(offset) * logical;
+        if *global != rendered {
+            *global = rendered;
+        }
+    }
+}
+
+/// Convert a world position to normalized device coordinates.
+fn world_to_ndc_proj(
+    world_pos: Vec2,
+    projection: Mat4,
+    camera_transform: &GlobalTransform,
+) -> Vec3 {
+    // Build a transform to convert from world to NDC using camera data
+    let world_to_ndc: Mat4 = projection * camera_transform.compute_matrix().inverse();
+    world_to_ndc.project_point3(world_pos.extend(0.0))
+}
+
+/// Convert a normalized device coordinate position to world space.
+fn ndc_to_world_proj(ndc: Vec2, projection: Mat4, camera_transform: &GlobalTransform) -> Vec2 {
+    let ndc_to_world: Mat4 = camera_transform.compute_matrix() * projection.inverse();
+    ndc_to_world.project_point3(ndc.extend(0.0)).truncate()
+}
+
+/// Convert a top left origin logical cursor position to a bottom left origin
+/// physical screen position.
+fn cursor_to_screen(cursor_pos: Vec2, window: &Window) -> Vec2 {
+    let pos = cursor_pos * window.scale_factor();
+    Vec2::new(pos.x, window.physical_height() as f32 - pos.y)
+}
+
+/// The viewport a camera would use for a given render target size.
+struct ViewportLayout {
+    /// Position of the viewport in render target pixels, counted from the top
+    /// left of the render target.
+    pos: Vec2,
+    size: Vec2,
+    zoom: f32,
+    /// The number of tiles visible in the viewport.
+    visible_tiles: Vec2,
+    /// The number of tiles the camera's grid is built around.
+    base_tiles: UVec2,
+    /// The size of the camera's `window_region` in render target pixels.
+    region_size: Vec2,
+}
+
+fn viewport_layout(tiled_cam: &TiledCamera, wres: UVec2) -> ViewportLayout {
+    let pixels_per_tile = tiled_cam.pixels_per_tile.max(UVec2::ONE);
+    let ppt = pixels_per_tile.as_vec2();
+    // The grid covers an overridden resolution, rounded up to whole tiles.
+    let res_override = tiled_cam
+        .target_resolution_override
+        .map(|r| r.max(UVec2::ONE));
+    let tile_count = match res_override {
+        Some(res) => (res.as_vec2() / ppt).ceil().as_uvec2(),
+        None => tiled_cam.tile_count.max(UVec2::ONE),
+    };
+
+    // Only the camera's region of the window is available to the viewport.
+    let region = tiled_cam.window_region;
+    let wres = wres.as_vec2();
+    let region_pos = (region.min * wres).floor();
+    let wres = ((region.max * wres).floor() - region_pos).max(Vec2::ONE);
+
+    // Margins are taken from the region, always leaving at least one pixel.
+    let m = tiled_cam.viewport_margins;
+    let margin_min = Vec2::new(m.left as f32, m.top as f32).min(wres - Vec2::ONE);
+    let margin_size = Vec2::new((m.left + m.right) as f32, (m.top + m.bottom) as f32);
+    let region_pos = region_pos + margin_min;
+    let wres = (wres - margin_size).max(Vec2::ONE);
+
+    let zoom = match tiled_cam.hidpi_mode {
+        HiDpiMode::Physical => tiled_cam.zoom_for_f32(wres),
+        HiDpiMode::Logical => {
+            let scale = tiled_cam.scale_factor.max(f32::EPSILON);
+            tiled_cam.zoom_for_f32(wres / scale) * scale
+        }
+    };
+
+    // Show fewer tiles if the minimum zoom won't fit in the window.
+    let base_tiles = match tiled_cam.min_zoom_policy {
+        MinZoomPolicy::Clip => tile_count,
+        MinZoomPolicy::ReduceTiles => {
+            let fit = (wres / (ppt * zoom)).floor().as_uvec2().max(UVec2::ONE);
+            tile_count.min(fit)
+        }
+    };
+    let tres = match res_override {
+        Some(res) if base_tiles == tile_count => res.as_vec2(),
+        _ => (base_tiles * pixels_per_tile).as_vec2(),
+    };
+
+    let (size, visible_tiles) = match tiled_cam.responsive_mode {
+        ResponsiveMode::Fixed | ResponsiveMode::FlexibleWidth => {
+            let vp_size = (tres * zoom).round();
+            (vp_size, tres / ppt)
+        }
+        ResponsiveMode::MinTiles => {
+            // Fill the window, unless it's too small to fit `tile_count` even
+            // at zoom 1 - then it's cropped the same as `Fixed`.
+            let vp_size = wres.max((tres * zoom).round());
+            (vp_size, vp_size / (ppt * zoom))
+        }
+    };
+
+    // The viewport can never be larger than the window, so any axis that
+    // doesn't fit is cropped, keeping the view centered.
+    let cropped = size.min(wres);
+    let visible_tiles = visible_tiles * cropped / size.max(Vec2::ONE);
+    let size = cropped;
+    let pos = tiled_cam.center_bias.viewport_pos(wres - size);
+
+    ViewportLayout {
+        pos: pos + region_pos,
+        size,
+        zoom,
+        visible_tiles,
+        base_tiles,
+        region_size: wres,
+    }
+}
+
+/// Whether a camera transform has no rotation around the z axis.
+fn is_axis_aligned(cam_transform: &GlobalTransform) -> bool {
+    let m = cam_transform.affine().matrix3;
+    m.x_axis.y.abs() <= f32::EPSILON && m.y_axis.x.abs() <= f32::EPSILON && m.x_axis.x > 0.0
+}
+
+/// The horizontal tile count that matches the aspect of the camera's region
+/// of the window, for [`ResponsiveMode::FlexibleWidth`].
+fn flexible_width(tiled_cam: &TiledCamera, wres: UVec2) -> u32 {
+    let region = viewport_layout(tiled_cam, wres).region_size;
+    let ppt = tiled_cam.pixels_per_tile.max(UVec2::ONE).as_vec2();
+    let height = tiled_cam.tile_count.y.max(1) as f32 * ppt.y;
+    let width = (height * region.x / region.y / ppt.x).floor();
+    (width as u32).max(1)
+}
+
+fn update_viewport(
+    tiled_cam: &mut TiledCamera,
+    wres: UVec2,
+    proj: &mut OrthographicProjection,
+    cam: &mut Camera,
+) {
+    let win_size = wres;
+    if tiled_cam.design_resolution.is_none() {
+        tiled_cam.design_resolution = Some(tiled_cam.target_resolution());
+    }
+    if tiled_cam.responsive_mode == ResponsiveMode::FlexibleWidth {
+        tiled_cam.tile_count.x = flexible_width(tiled_cam, wres);
+    }
+    let layout = viewport_layout(tiled_cam, wres);
+
+    // Only warn once, not on every update while the settings are invalid.
+    if tiled_cam.is_valid() {
+        tiled_cam.warned_invalid = false;
+    } else if !tiled_cam.warned_invalid {
+        tiled_cam.warned_invalid = true;
+        warn!(
+            "TiledCamera has a zero tile_count ({}) or pixels_per_tile ({}), treating it as 1",
+            tiled_cam.tile_count, tiled_cam.pixels_per_tile
+        );
+    }
+    tiled_cam.grid.pixels_per_tile = tiled_cam.pixels_per_tile.max(UVec2::ONE);
+    tiled_cam.grid.world_space = tiled_cam.world_space();
+
+    // The visible area in world units.
+    let world_size = layout.visible_tiles * tiled_cam.cell_size_world();
+
+    // The 'size' of the orthographic projection.
+    //
+    // This refers to the size of the projection along the scaled axis.
+    let ortho_size = match tiled_cam.resolved_scale_axis(layout.region_size.as_uvec2()) {
+        ScaleAxis::Horizontal => {
+            proj.scaling_mode = ScalingMode::FixedHorizontal(world_size.x);
+            world_size.x
+        }
+        _ => {
+            proj.scaling_mode = ScalingMode::FixedVertical(world_size.y);
+            world_size.y
+        }
+    };
+
+    proj.viewport_origin = tiled_cam.camera_anchor;
+
+    cam.viewport = Some(Viewport {
+        physical_position: layout.pos.as_uvec2(),
+        physical_size: layout.size.as_uvec2(),
+        ..default()
+    });
+
+    // Camera values may have been changed manually - update grid values.
+    tiled_cam.grid.tile_count = grid_tile_count(layout.visible_tiles, layout.base_tiles);
+    tiled_cam.zoom = layout.zoom;
+    tiled_cam.vp_pos = layout.pos.as_uvec2();
+    tiled_cam.vp_size = layout.size.as_uvec2();
+    tiled_cam.win_size = win_size;
+
+    #[cfg(feature = "startup_log")]
+    if let Some(message) = startup_log_message(tiled_cam) {
+        bevy::log::info!("{}", message);
+    }
+
+    if !tiled_cam.initialized && tiled_cam.defer_until_window {
+        cam.is_active = true;
+    }
+    tiled_cam.initialized = true;
+    tiled_cam.ortho_size = ortho_size;
+}
+
+/// The message to log for a camera's first viewport update, or [`None`] if
+/// the camera has already been initialized.
+#[cfg(feature = "startup_log")]
+fn startup_log_message(tiled_cam: &TiledCamera) -> Option<String> {
+    if tiled_cam.initialized {
+        return None;
+    }
+    Some(format!(
+        "TiledCamera initialized - target_resolution: {}, window_res: {}, zoom: {}, vp_pos: {}, vp_size: {}",
+        tiled_cam.target_resolution(),
+        tiled_cam.win_size,
+        tiled_cam.zoom,
+        tiled_cam.vp_pos,
+        tiled_cam.vp_size
+    ))
+}
+
+/// The number of whole grid tiles needed to cover `visible` tiles.
+///
+/// The result keeps the same parity as `tile_count` on each axis so tile
+/// boundaries don't shift relative to the camera when more tiles are revealed.
+fn grid_tile_count(visible: Vec2, tile_count: UVec2) -> UVec2 {
+    let count = visible.ceil().as_uvec2().max(tile_count);
+    let parity_differs = (count % 2).cmpne(tile_count % 2);
+    UVec2::select(parity_differs, count + 1, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use bevy::{
+        math::Quat, prelude::Update, render::camera::CameraProjection, window::WindowResolution,
+    };
+
+    use super::*;
+
+    fn unit_cam(pos: impl Point2d, tile_count: impl Size2d) -> (GlobalTransform, TiledCamera) {
+        (
+            GlobalTransform::from_translation(pos.as_vec2().extend(0.0)),
+            TiledCamera::unit_cam(tile_count, [8, 8]),
+        )
+    }
+
+    fn make_pixel_cam(
+        pos: impl Point2d,
+        tile_count: impl Size2d,
+    ) -> (GlobalTransform, TiledCamera) {
+        (
+            GlobalTransform::from_translation(pos.as_vec2().extend(0.0)),
+            TiledCamera::pixel_cam(tile_count, [8, 8]),
+        )
+    }
+
+    #[test]
+    fn world_to_index() {
+        let (t, cam) = unit_cam([5.0, 5.0], [3, 3]);
+        let p = cam.world_to_index(&t, [4.5, 4.5]);
+        assert_eq!([0, 0], p.to_array());
+
+        let (t, cam) = unit_cam([5.0, 5.0], [4, 4]);
+        let p = cam.world_to_index(&t, [4.5, 4.5]);
+        assert_eq!([-1, -1], p.to_array());
+
+        let (t, cam) = make_pixel_cam([16.0, 16.0], [3, 3]);
+        let p = cam.world_to_index(&t, [12.0, 12.0]);
+        assert_eq!([0, 0], p.to_array());
+
+        let (t, cam) = make_pixel_cam([16.0, 16.0], [4, 4]);
+        let p = cam.world_to_index(&t, [12.0, 12.0]);
+        assert_eq!([-1, -1], p.to_array());
+    }
+
+    #[test]
+    fn index_to_world() {
+        let (t, cam) = make_pixel_cam([5, 5], [4, 4]);
+        let p = cam.index_to_tile_pos(&t, [3, 3]);
+        assert_eq!([29.0, 29.0], p.to_array());
+
+        let (t, cam) = unit_cam([5, 5], [4, 4]);
+        let p = cam.index_to_tile_pos(&t, [3, 3]);
+        assert_eq!([8.0, 8.0], p.to_array());
+
+        let (t, cam) = make_pixel_cam([16, 16], [3, 3]);
+        let p = cam.index_to_tile_pos(&t, [3, 3]);
+        assert_eq!([36.0, 36.0], p.to_array());
+    }
+
+    fn min_tiles_viewport(wres: impl Size2d) -> TiledCamera {
+        let mut cam = TiledCamera::unit_cam([10, 6], [8, 8]);
+        cam.responsive_mode = ResponsiveMode::MinTiles;
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(&mut cam, wres.as_uvec2(), &mut proj, &mut camera);
+        cam
+    }
+
+    #[test]
+    fn min_tiles() {
+        for wres in [[80, 48], [100, 48], [250, 130], [1920, 1080], [1000, 1000]] {
+            let cam = min_tiles_viewport(wres);
+            assert_eq!(wres, cam.viewport_size().to_array());
+            assert_eq!(UVec2::ZERO, cam.viewport_pos());
+
+            let visible = cam.viewport_size() / (cam.pixels_per_tile * cam.zoom());
+            assert!(visible.cmpge(cam.tile_count).all());
+            assert!(cam.world_grid().tile_count.cmpge(visible).all());
+            assert_eq!(cam.tile_count % 2, cam.world_grid().tile_count % 2);
+        }
+
+        let cam = min_tiles_viewport([250, 130]);
+        assert_eq!(2, cam.zoom());
+        assert_eq!([16, 10], cam.world_grid().tile_count.to_array());
+    }
+
+    #[test]
+    fn import_position() {
+        let cam = TiledCamera::pixel_cam([4, 4], [4, 8]);
+        let p = cam.import_position(Vec2::new(1.5, -2.0), WorldSpace::Units);
+        assert_eq!([12.0, -16.0], p.to_array());
+        let p = cam.import_position(Vec2::new(12.0, -16.0), WorldSpace::Pixels);
+        assert_eq!([12.0, -16.0], p.to_array());
+
+        let cam = TiledCamera::unit_cam([4, 4], [4, 8]);
+        let p = cam.import_position(Vec2::new(12.0, -16.0), WorldSpace::Pixels);
+        assert_eq!([1.5, -2.0], p.to_array());
+        let p = cam.import_position(Vec2::new(1.5, -2.0), WorldSpace::Units);
+        assert_eq!([1.5, -2.0], p.to_array());
+    }
+
+    #[test]
+    fn screen_in_viewport() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(&mut cam, UVec2::new(200, 160), &mut proj, &mut camera);
+        assert_eq!([20, 0], cam.viewport_pos().to_array());
+
+        assert!(cam.screen_in_viewport(Vec2::new(20.0, 0.0)));
+        assert!(cam.screen_in_viewport(Vec2::new(100.0, 80.0)));
+        assert!(cam.screen_in_viewport(Vec2::new(179.5, 159.5)));
+
+        assert!(!cam.screen_in_viewport(Vec2::new(10.0, 80.0)));
+        assert!(!cam.screen_in_viewport(Vec2::new(180.0, 80.0)));
+        assert!(!cam.screen_in_viewport(Vec2::new(100.0, 160.0)));
+    }
+
+    #[test]
+    fn pan_limits() {
+        let mut cam = TiledCamera::unit_cam([10, 6], [8, 8]);
+        assert_eq!(None, cam.pan_limits());
+
+        cam.bounds = Some(Rect::new(-20.0, -10.0, 20.0, 10.0));
+        let limits = cam.pan_limits().unwrap();
+        assert_eq!([-15.0, -7.0], limits.min.to_array());
+        assert_eq!([15.0, 7.0], limits.max.to_array());
+
+        cam.bounds = Some(Rect::new(0.0, 0.0, 8.0, 20.0));
+        assert_eq!(None, cam.pan_limits());
+
+        let mut cam = TiledCamera::pixel_cam([10, 6], [8, 8]);
+        cam.bounds = Some(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let limits = cam.pan_limits().unwrap();
+        assert_eq!([40.0, 24.0], limits.min.to_array());
+        assert_eq!([60.0, 76.0], limits.max.to_array());
+    }
+
+    #[test]
+    fn indices_to_centers() {
+        let indices: Vec<_> = (-3..3)
+            .flat_map(|y| (-3..3).map(move |x| IVec2::new(x, y)))
+            .collect();
+        for (t, cam) in [unit_cam([5, 3], [4, 3]), make_pixel_cam([-16, 8], [5, 4])] {
+            let mut centers = Vec::new();
+            cam.indices_to_centers(&t, &indices, &mut centers);
+            assert_eq!(indices.len(), centers.len());
+            for (&i, &c) in indices.iter().zip(centers.iter()) {
+                assert_eq!(cam.index_to_tile_center(&t, i), c);
+            }
+        }
+    }
+
+    #[test]
+    fn center_bias() {
+        // 81 x 83 window, 40 x 40 viewport at zoom 2 - leftover of 1 x 3 pixels.
+        let bars = |bias| {
+            let mut cam = TiledCamera::unit_cam([5, 5], [8, 8]);
+            cam.center_bias = bias;
+            let mut proj = OrthographicProjection::default();
+            let mut camera = Camera::default();
+            let wres = UVec2::new(81, 83);
+            update_viewport(&mut cam, wres, &mut proj, &mut camera);
+            let low = cam.viewport_pos();
+            let high = wres - low - cam.viewport_size();
+            (low.to_array(), high.to_array())
+        };
+
+        assert_eq!(([0, 1], [1, 2]), bars(CenterBias::FloorLow));
+        assert_eq!(([1, 2], [0, 1]), bars(CenterBias::Round));
+        assert_eq!(([1, 1], [0, 2]), bars(CenterBias::SplitEven));
+    }
+
+    #[test]
+    fn render_offset() {
+        let mut app = App::new();
+        app.add_systems(Update, update_render_transform);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.render_offset = Vec2::new(0.5, -2.0);
+        let entity = app
+            .world
+            .spawn((
+                cam,
+                Transform::from_xyz(3.0, 4.0, 0.0),
+                GlobalTransform::default(),
+            ))
+            .id();
+
+        for _ in 0..2 {
+            app.update();
+            let cam = app.world.get::<TiledCamera>(entity).unwrap();
+            let global = app.world.get::<GlobalTransform>(entity).unwrap();
+            assert_eq!([3.0, 4.0], cam.logical_position().to_array());
+            assert_eq!([3.5, 2.0, 0.0], global.translation().to_array());
+        }
+
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .x = 5.0;
+        app.update();
+        let cam = app.world.get::<TiledCamera>(entity).unwrap();
+        let global = app.world.get::<GlobalTransform>(entity).unwrap();
+        assert_eq!([5.0, 4.0], cam.logical_position().to_array());
+        assert_eq!([5.5, 2.0, 0.0], global.translation().to_array());
+    }
+
+    #[test]
+    fn visibility_delta() {
+        let cam = TiledCamera::unit_cam([4, 3], [8, 8]);
+
+        let (entered, exited) = cam.visibility_delta(IVec2::ZERO, IVec2::ZERO);
+        assert!(entered.is_empty() && exited.is_empty());
+
+        let (entered, exited) = cam.visibility_delta(IVec2::ZERO, IVec2::Y);
+        let entered: Vec<_> = entered.iter().map(|i| i.to_array()).collect();
+        let exited: Vec<_> = exited.iter().map(|i| i.to_array()).collect();
+        assert_eq!(vec![[-2, 2], [-1, 2], [0, 2], [1, 2]], entered);
+        assert_eq!(vec![[-2, -1], [-1, -1], [0, -1], [1, -1]], exited);
+
+        let (entered, exited) = cam.visibility_delta(IVec2::ZERO, IVec2::new(10, 0));
+        assert_eq!(12, entered.len());
+        assert_eq!(12, exited.len());
+    }
+
+    #[test]
+    fn position_in_tiles() {
+        let t = GlobalTransform::from_xyz(12.0, -6.0, 0.0);
+
+        let cam = TiledCamera::unit_cam([4, 4], [4, 8]);
+        assert_eq!([24.0, -6.0], cam.position_in_tiles(&t).to_array());
+
+        let cam = TiledCamera::pixel_cam([4, 4], [4, 8]);
+        assert_eq!([3.0, -0.75], cam.position_in_tiles(&t).to_array());
+    }
+
+    /// Run the viewport update and return the resulting projection matrix.
+    fn projection(cam: &mut TiledCamera, wres: impl Size2d) -> Mat4 {
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(cam, wres.as_uvec2(), &mut proj, &mut camera);
+        let vp = cam.viewport_size().as_vec2();
+        proj.update(vp.x, vp.y);
+        proj.get_projection_matrix()
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn verify_conversions() {
+        let t = GlobalTransform::from_xyz(3.0, 2.0, 0.0);
+        let screen_pos = Vec2::new(100.0, 60.0);
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.verify_conversions = true;
+        let proj = projection(&mut cam, [200, 160]);
+        assert!(cam.verify_round_trip(screen_pos, proj, &t));
+
+        let mut cam = TiledCamera::pixel_cam([10, 10], [8, 8]);
+        cam.verify_conversions = true;
+        let proj = projection(&mut cam, [200, 160]);
+        assert!(cam.verify_round_trip(screen_pos, proj, &t));
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [4, 8]);
+        cam.verify_conversions = true;
+        let proj = projection(&mut cam, [200, 160]);
+        assert!(cam.verify_round_trip(screen_pos, proj, &t));
+
+        // A collapsed camera transform can't be inverted, so screen positions
+        // don't survive the round trip.
+        let collapsed = GlobalTransform::from_scale(Vec3::ZERO);
+        assert!(!cam.verify_round_trip(screen_pos, proj, &collapsed));
+
+        // Nothing is checked with verification disabled.
+        cam.verify_conversions = false;
+        assert!(cam.verify_round_trip(screen_pos, proj, &collapsed));
+    }
+
+    #[test]
+    fn tile_render_size() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [4, 8]);
+        projection(&mut cam, [80, 160]);
+        assert_eq!(2, cam.zoom());
+        assert_eq!([8, 16], cam.tile_render_size().to_array());
+
+        projection(&mut cam, [130, 250]);
+        assert_eq!(3, cam.zoom());
+        assert_eq!([12, 24], cam.tile_render_size().to_array());
+    }
+
+    #[test]
+    fn window_region() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let wres = [1000, 500];
+
+        let mut left = TiledCamera::unit_cam([10, 10], [8, 8]);
+        left.window_region = Rect::new(0.0, 0.0, 0.7, 1.0);
+        let left_proj = projection(&mut left, wres);
+        assert_eq!([110, 10], left.viewport_pos().to_array());
+        assert_eq!([480, 480], left.viewport_size().to_array());
+
+        let mut right = TiledCamera::unit_cam([10, 10], [8, 8]);
+        right.window_region = Rect::new(0.7, 0.0, 1.0, 1.0);
+        let right_proj = projection(&mut right, wres);
+        assert_eq!([730, 130], right.viewport_pos().to_array());
+        assert_eq!([240, 240], right.viewport_size().to_array());
+
+        let left_center = Vec2::new(350.0, 250.0);
+        let right_center = Vec2::new(850.0, 250.0);
+        assert!(left.screen_in_viewport(left_center));
+        assert!(!left.screen_in_viewport(right_center));
+        assert!(right.screen_in_viewport(right_center));
+        assert!(!right.screen_in_viewport(left_center));
+
+        let p = left
+            .screen_to_world_proj(left_center, left_proj, &t)
+            .unwrap();
+        assert_eq!([5.0, 5.0], p.to_array());
+        let p = right
+            .screen_to_world_proj(right_center, right_proj, &t)
+            .unwrap();
+        assert_eq!([5.0, 5.0], p.to_array());
+    }
+
+    #[test]
+    fn suggest_config() {
+        let check = |window: [u32; 2], desired: u32, world: [u32; 2]| {
+            let (ppt, tile_count) =
+                TiledCamera::suggest_config(window.into(), UVec2::splat(desired), world.into());
+            let mut cam = TiledCamera::unit_cam(tile_count, ppt);
+            projection(&mut cam, window);
+            cam.tile_render_size()
+        };
+
+        assert_eq!([32, 32], check([1920, 1080], 32, [1000, 1000]).to_array());
+        assert_eq!([24, 24], check([1280, 720], 24, [1000, 1000]).to_array());
+
+        let size = check([1000, 700], 30, [1000, 1000]);
+        assert!(size.cmpge(UVec2::splat(25)).all() && size.cmple(UVec2::splat(35)).all());
+    }
+
+    #[test]
+    fn reset() {
+        let mut cam = TiledCamera::pixel_cam([10, 10], [16, 16]);
+        cam.window_region = Rect::new(0.5, 0.0, 1.0, 1.0);
+        projection(&mut cam, [400, 300]);
+        let (vp_pos, vp_size) = (cam.viewport_pos(), cam.viewport_size());
+
+        cam.responsive_mode = ResponsiveMode::MinTiles;
+        cam.bounds = Some(Rect::new(0.0, 0.0, 10.0, 10.0));
+        cam.render_offset = Vec2::ONE;
+        cam.reset();
+
+        let default = TiledCamera::default();
+        assert_eq!(default.pixels_per_tile, cam.pixels_per_tile);
+        assert_eq!(default.tile_count, cam.tile_count);
+        assert_eq!(default.world_space(), cam.world_space());
+        assert_eq!(default.responsive_mode, cam.responsive_mode);
+        assert_eq!(None, cam.bounds);
+        assert_eq!(Vec2::ZERO, cam.render_offset);
+
+        assert_eq!(Rect::new(0.5, 0.0, 1.0, 1.0), cam.window_region);
+        assert_eq!(vp_pos, cam.viewport_pos());
+        assert_eq!(vp_size, cam.viewport_size());
+    }
+
+    #[test]
+    fn reset_keeps_builder_settings() {
+        let mut cam = TiledCameraBundle::unit_cam([10, 10])
+            .with_defer_until_window(true)
+            .with_fixed_viewport_after_init()
+            .tiled_camera;
+        cam.reset();
+
+        assert!(cam.defer_until_window);
+        assert!(cam.fixed_viewport);
+    }
+
+    #[test]
+    fn coverage() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        projection(&mut cam, [200, 160]);
+        assert_eq!(0.8, cam.coverage(UVec2::new(200, 160)));
+
+        projection(&mut cam, [160, 160]);
+        assert_eq!(1.0, cam.coverage(UVec2::new(160, 160)));
+
+        // Viewport is cropped to a window smaller than the target resolution.
+        projection(&mut cam, [40, 40]);
+        assert_eq!(1.0, cam.coverage(UVec2::new(40, 40)));
+    }
+
+    #[test]
+    fn grid_lines_world() {
+        let (t, cam) = make_pixel_cam([8, 0], [4, 3]);
+        let lines: Vec<_> = cam.grid_lines_world(&t).collect();
+        assert_eq!(5 + 4, lines.len());
+        assert_eq!(
+            ([-8.0, -12.0], [-8.0, 12.0]),
+            (lines[0].0.to_array(), lines[0].1.to_array())
+        );
+        assert_eq!(
+            ([-8.0, 12.0], [24.0, 12.0]),
+            (lines[8].0.to_array(), lines[8].1.to_array())
+        );
+
+        let (t, cam) = unit_cam([0, 0], [7, 5]);
+        assert_eq!(8 + 6, cam.grid_lines_world(&t).count());
+    }
+
+    #[test]
+    fn frame_point_with_tiles() {
+        let point = Vec2::new(13.5, -4.25);
+        for count in [[5, 3], [4, 6]] {
+            let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+            let mut transform = Transform::from_xyz(0.0, 0.0, 10.0);
+            cam.frame_point_with_tiles(point, count.into(), &mut transform);
+
+            let t = GlobalTransform::from(transform);
+            assert_eq!(IVec2::ZERO, cam.world_to_index(&t, point));
+            assert_eq!(count, cam.world_grid().tile_count.to_array());
+            assert_eq!(count[0] * count[1], cam.tile_center_iter(&t).count() as u32);
+            assert_eq!(10.0, transform.translation.z);
+        }
+    }
+
+    #[test]
+    fn edge_midpoints_world() {
+        let (t, cam) = unit_cam([2.0, 1.0], [8, 6]);
+        let [l, r, b, t] = cam.edge_midpoints_world(&t).map(|p| p.to_array());
+        assert_eq!(
+            [[-2.0, 1.0], [6.0, 1.0], [2.0, -2.0], [2.0, 4.0]],
+            [l, r, b, t]
+        );
+
+        let (t, cam) = make_pixel_cam([0.0, 0.0], [3, 3]);
+        let [l, r, b, t] = cam.edge_midpoints_world(&t).map(|p| p.to_array());
+        assert_eq!(
+            [[-12.0, 0.0], [12.0, 0.0], [0.0, -12.0], [0.0, 12.0]],
+            [l, r, b, t]
+        );
+    }
+
+    fn app_with_window(wres: [f32; 2]) -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Assets<Image>>()
+            .add_event::<WindowResized>()
+            .add_plugins(TiledCameraPlugin);
+        let window = Window {
+            resolution: WindowResolution::new(wres[0], wres[1]),
+            ..default()
+        };
+        app.world.spawn((window, PrimaryWindow));
+        app
+    }
+
+    fn spawn_cam(app: &mut App, cam: TiledCamera) -> Entity {
+        app.world
+            .spawn((
+                cam,
+                Camera::default(),
+                OrthographicProjection::default(),
+                Transform::default(),
+                GlobalTransform::default(),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn deferred_viewport_update() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let entity = spawn_cam(&mut app, TiledCamera::unit_cam([10, 10], [8, 8]));
+        app.add_systems(Update, |mut q: Query<&mut TiledCamera>| {
+            q.single_mut().tile_count = UVec2::new(4, 4);
+        });
+        app.add_systems(Update, |mut q: Query<&mut TiledCamera>| {
+            q.single_mut().pixels_per_tile = UVec2::new(4, 4);
+        });
+        app.update();
+
+        let cam = app.world.get::<TiledCamera>(entity).unwrap();
+        assert_eq!(10, cam.zoom());
+        assert_eq!([160, 160], cam.viewport_size().to_array());
+        let viewport = app.world.get::<Camera>(entity).unwrap().viewport.clone();
+        assert_eq!([20, 0], viewport.unwrap().physical_position.to_array());
+    }
+
+    #[test]
+    fn defer_until_window() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Assets<Image>>()
+            .add_event::<WindowResized>()
+            .add_plugins(TiledCameraPlugin);
+        let bundle = TiledCameraBundle::unit_cam([10, 10]).with_defer_until_window(true);
+        let entity = app.world.spawn(bundle).id();
+
+        app.update();
+        assert!(!app.world.get::<Camera>(entity).unwrap().is_active);
+        assert!(!app
+            .world
+            .get::<TiledCamera>(entity)
+            .unwrap()
+            .is_initialized());
+
+        let window = Window {
+            resolution: WindowResolution::new(200.0, 160.0),
+            ..default()
+        };
+        let window = app.world.spawn((window, PrimaryWindow)).id();
+        app.world.send_event(WindowResized {
+            window,
+            width: 200.0,
+            height: 160.0,
+        });
+        app.update();
+        assert!(app.world.get::<Camera>(entity).unwrap().is_active);
+        let cam = app.world.get::<TiledCamera>(entity).unwrap();
+        assert!(cam.is_initialized());
+        assert_eq!([160, 160], cam.viewport_size().to_array());
+    }
+
+    #[test]
+    fn letterbox_rects() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        projection(&mut cam, [200, 160]);
+        let bars = cam.letterbox_rects(UVec2::new(200, 160));
+        assert_eq!(
+            vec![
+                Rect::new(0.0, 0.0, 20.0, 160.0),
+                Rect::new(180.0, 0.0, 200.0, 160.0)
+            ],
+            bars
+        );
+
+        projection(&mut cam, [170, 175]);
+        let bars = cam.letterbox_rects(UVec2::new(170, 175));
+        assert_eq!(
+            vec![
+                Rect::new(0.0, 0.0, 5.0, 175.0),
+                Rect::new(165.0, 0.0, 170.0, 175.0),
+                Rect::new(5.0, 0.0, 165.0, 7.0),
+                Rect::new(5.0, 167.0, 165.0, 175.0),
+            ],
+            bars
+        );
+
+        projection(&mut cam, [160, 160]);
+        assert!(cam.letterbox_rects(UVec2::new(160, 160)).is_empty());
+    }
+
+    #[test]
+    fn align_sprite_position() {
+        let cam = TiledCamera::pixel_cam([10, 10], [8, 8]);
+        let align = |pos: [f32; 2], size: [u32; 2], anchor| {
+            cam.align_sprite_position(pos.into(), size.into(), anchor)
+                .to_array()
+        };
+        assert_eq!([-0.5, -0.5], align([0.0, 0.0], [3, 3], Anchor::Center));
+        assert_eq!([0.0, 0.0], align([0.2, -0.2], [4, 4], Anchor::Center));
+        assert_eq!([0.0, 1.0], align([0.3, 0.6], [3, 5], Anchor::BottomLeft));
+        assert_eq!([10.0, 10.0], align([10.2, 10.2], [3, 5], Anchor::TopRight));
+        assert_eq!([2.0, 1.5], align([2.1, 1.6], [2, 3], Anchor::CenterLeft));
+
+        let cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let p = cam.align_sprite_position(Vec2::ZERO, UVec2::new(3, 4), Anchor::Center);
+        assert_eq!([-0.0625, 0.0], p.to_array());
+    }
+
+    #[test]
+    fn forced_zoom() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.set_forced_zoom(Some(3));
+        projection(&mut cam, [200, 160]);
+        assert_eq!(3, cam.zoom());
+        assert_eq!([200, 160], cam.viewport_size().to_array());
+        assert_eq!(UVec2::ZERO, cam.viewport_pos());
+        assert!(cam.coverage(UVec2::new(200, 160)) == 1.0);
+        // Each tile is still 24 pixels on screen.
+        let size = cam.visible_world_size() * 24.0;
+        assert!(size.abs_diff_eq(Vec2::new(200.0, 160.0), 1e-3));
+
+        cam.set_forced_zoom(Some(1));
+        projection(&mut cam, [200, 160]);
+        assert_eq!([80, 80], cam.viewport_size().to_array());
+        assert_eq!([60, 40], cam.viewport_pos().to_array());
+
+        cam.set_forced_zoom(None);
+        projection(&mut cam, [200, 160]);
+        assert_eq!(2, cam.zoom());
+    }
+
+    #[test]
+    fn grid_region_to_world() {
+        let (t, cam) = make_pixel_cam([8, 8], [4, 4]);
+        let points = [[0, 0], [1, 0], [-2, 1]];
+        let world: Vec<_> = cam.grid_region_to_world(&t, points).collect();
+        assert_eq!(3, world.len());
+        for (p, w) in points.iter().zip(world) {
+            assert_eq!(cam.index_to_tile_center(&t, *p), w);
+        }
+    }
+
+    #[test]
+    fn fixed_viewport_after_init() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let bundle = TiledCameraBundle::unit_cam([10, 10]).with_fixed_viewport_after_init();
+        let entity = app.world.spawn(bundle).id();
+        app.update();
+
+        let (window, mut window_comp) = app
+            .world
+            .query_filtered::<(Entity, &mut Window), With<PrimaryWindow>>()
+            .single_mut(&mut app.world);
+        window_comp.resolution.set(400.0, 400.0);
+        app.world.send_event(WindowResized {
+            window,
+            width: 400.0,
+            height: 400.0,
+        });
+        app.update();
+
+        let cam = app.world.get::<TiledCamera>(entity).unwrap();
+        assert_eq!(2, cam.zoom());
+        assert_eq!([20, 0], cam.viewport_pos().to_array());
+        assert_eq!([160, 160], cam.viewport_size().to_array());
+
+        // Changing the camera still works, using the initial window size.
+        app.world.get_mut::<TiledCamera>(entity).unwrap().tile_count = UVec2::new(5, 5);
+        app.update();
+        let cam = app.world.get::<TiledCamera>(entity).unwrap();
+        assert_eq!(4, cam.zoom());
+        assert_eq!([20, 0], cam.viewport_pos().to_array());
+    }
+
+    #[test]
+    fn row_column_rect_world() {
+        let (t, cam) = unit_cam([10.0, 0.0], [4, 3]);
+        let row = cam.row_rect_world(&t, 1).unwrap();
+        assert_eq!(Rect::new(8.0, 0.5, 12.0, 1.5), row);
+        let row = cam.row_rect_world(&t, -1).unwrap();
+        assert_eq!(Rect::new(8.0, -1.5, 12.0, -0.5), row);
+        assert_eq!(None, cam.row_rect_world(&t, 2));
+        assert_eq!(None, cam.row_rect_world(&t, -2));
+
+        let col = cam.column_rect_world(&t, -2).unwrap();
+        assert_eq!(Rect::new(8.0, -1.5, 9.0, 1.5), col);
+        let col = cam.column_rect_world(&t, 1).unwrap();
+        assert_eq!(Rect::new(11.0, -1.5, 12.0, 1.5), col);
+        assert_eq!(None, cam.column_rect_world(&t, 2));
+        assert_eq!(None, cam.column_rect_world(&t, -3));
+
+        let (t, cam) = make_pixel_cam([0.0, 0.0], [2, 2]);
+        let row = cam.row_rect_world(&t, 0).unwrap();
+        assert_eq!(Rect::new(-8.0, 0.0, 8.0, 8.0), row);
+    }
+
+    #[test]
+    fn as_orthographic() {
+        for mut cam in [
+            TiledCamera::unit_cam([10, 6], [4, 8]),
+            TiledCamera::pixel_cam([10, 6], [4, 8]),
+        ] {
+            projection(&mut cam, [300, 200]);
+            let ortho = cam.as_orthographic();
+            assert_eq!(cam.ortho_extents(), ortho.area);
+
+            // Matches the projection set by the plugin.
+            let mut proj = OrthographicProjection::default();
+            update_viewport(
+                &mut cam,
+                UVec2::new(300, 200),
+                &mut proj,
+                &mut Camera::default(),
+            );
+            let vp = cam.viewport_size().as_vec2();
+            proj.update(vp.x, vp.y);
+            assert_eq!(proj.area, ortho.area);
+        }
+
+        let mut cam = TiledCamera::unit_cam([10, 6], [4, 8]);
+        projection(&mut cam, [300, 200]);
+        assert_eq!(Rect::new(-2.5, -3.0, 2.5, 3.0), cam.ortho_extents());
+
+        // Anchored to the bottom left, with a custom depth range.
+        let mut cam = TiledCameraBundle::unit_cam([10, 6])
+            .with_pixels_per_tile([4, 8])
+            .with_camera_anchor(Vec2::ZERO)
+            .with_depth_range(-10.0, 50.0)
+            .tiled_camera;
+        projection(&mut cam, [300, 200]);
+        assert_eq!(Rect::new(0.0, 0.0, 5.0, 6.0), cam.ortho_extents());
+        let ortho = cam.as_orthographic();
+        assert_eq!(Vec2::ZERO, ortho.viewport_origin);
+        assert_eq!((-10.0, 50.0), (ortho.near, ortho.far));
+
+        let mut proj = OrthographicProjection::default();
+        update_viewport(
+            &mut cam,
+            UVec2::new(300, 200),
+            &mut proj,
+            &mut Camera::default(),
+        );
+        let vp = cam.viewport_size().as_vec2();
+        proj.update(vp.x, vp.y);
+        assert_eq!(proj.area, ortho.area);
+        assert_eq!(proj.viewport_origin, ortho.viewport_origin);
+    }
+
+    #[test]
+    fn pick() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+
+        let pick = cam.pick_proj(Vec2::new(108.0, 88.0), proj, &t).unwrap();
+        assert_eq!([5.5, 5.5], pick.world.to_array());
+        assert_eq!([0, 0], pick.tile_index.to_array());
+        assert_eq!([5.5, 5.5], pick.tile_center.to_array());
+        assert!(pick.in_bounds);
+
+        let pick = cam.pick_proj(Vec2::new(20.0, 0.0), proj, &t).unwrap();
+        assert_eq!([-5, -5], pick.tile_index.to_array());
+        assert_eq!([0.5, 0.5], pick.tile_center.to_array());
+        assert!(pick.in_bounds);
+
+        // Letterbox bar
+        assert_eq!(None, cam.pick_proj(Vec2::new(10.0, 80.0), proj, &t));
+
+        // The grid was shrunk but the viewport hasn't been updated yet.
+        let mut transform = Transform::from_xyz(5.0, 5.0, 0.0);
+        cam.frame_point_with_tiles(Vec2::new(5.0, 5.0), UVec2::new(2, 2), &mut transform);
+        let pick = cam.pick_proj(Vec2::new(20.0, 0.0), proj, &t).unwrap();
+        assert!(!pick.in_bounds);
+    }
+
+    #[test]
+    fn stream_bounds() {
+        let (t, cam) = unit_cam([2.0, 1.0], [8, 6]);
+        assert_eq!(Rect::new(-2.0, -2.0, 6.0, 4.0), cam.visible_world_rect(&t));
+        assert_eq!(Rect::new(-2.0, -2.0, 6.0, 4.0), cam.stream_bounds(&t, 0));
+        assert_eq!(Rect::new(-4.0, -4.0, 8.0, 6.0), cam.stream_bounds(&t, 2));
+
+        let (t, cam) = make_pixel_cam([0.0, 0.0], [4, 2]);
+        assert_eq!(
+            Rect::new(-16.0, -8.0, 16.0, 8.0),
+            cam.visible_world_rect(&t)
+        );
+        assert_eq!(
+            Rect::new(-24.0, -16.0, 24.0, 16.0),
+            cam.stream_bounds(&t, 1)
+        );
+    }
+
+    #[test]
+    fn suspend() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let entity = app.world.spawn(TiledCameraBundle::unit_cam([10, 10])).id();
+        app.update();
+        let viewport = app.world.get::<Camera>(entity).unwrap().viewport.clone();
+        assert_eq!([160, 160], viewport.unwrap().physical_size.to_array());
+
+        app.world.get_mut::<TiledCamera>(entity).unwrap().suspend();
+        app.update();
+        assert!(app.world.get::<Camera>(entity).unwrap().viewport.is_none());
+        let proj = app.world.get::<OrthographicProjection>(entity).unwrap();
+        assert!(matches!(proj.scaling_mode, ScalingMode::WindowSize(_)));
+
+        app.world.get_mut::<TiledCamera>(entity).unwrap().resume();
+        app.update();
+        let viewport = app.world.get::<Camera>(entity).unwrap().viewport.clone();
+        assert_eq!([160, 160], viewport.unwrap().physical_size.to_array());
+        let proj = app.world.get::<OrthographicProjection>(entity).unwrap();
+        assert!(matches!(proj.scaling_mode, ScalingMode::FixedVertical(_)));
+    }
+
+    #[test]
+    fn metatiles() {
+        let (t, mut cam) = unit_cam([0.0, 0.0], [4, 4]);
+        cam.metatile_size = UVec2::new(2, 2);
+
+        let p = Vec2::new(1.5, -0.5);
+        assert_eq!([1, -1], cam.world_to_index(&t, p).to_array());
+        assert_eq!([0, -1], cam.world_to_metatile_index(&t, p).to_array());
+
+        let p = Vec2::new(-1.5, 1.5);
+        assert_eq!([-2, 1], cam.world_to_index(&t, p).to_array());
+        assert_eq!([-1, 0], cam.world_to_metatile_index(&t, p).to_array());
+
+        let centers: Vec<_> = cam.metatile_center_iter(&t).map(|p| p.to_array()).collect();
+        assert_eq!(
+            vec![[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0], [1.0, 1.0]],
+            centers
+        );
+
+        // Odd tile count - metatiles overhang the edge of the grid.
+        let (t, mut cam) = make_pixel_cam([0.0, 0.0], [3, 3]);
+        cam.metatile_size = UVec2::new(2, 2);
+        assert_eq!(4, cam.metatile_center_iter(&t).count());
+        assert_eq!(
+            [-1, -1],
+            cam.world_to_metatile_index(&t, [-10.0, -10.0]).to_array()
+        );
+    }
+
+    #[test]
+    fn recommended_ui_scale() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        projection(&mut cam, [160, 160]);
+        assert_eq!(2, cam.zoom());
+        assert_eq!(2.0, cam.recommended_ui_scale(1.0));
+        assert_eq!(1.0, cam.recommended_ui_scale(2.0));
+    }
+
+    #[test]
+    fn visible_chunks() {
+        let (t, cam) = unit_cam([16.0, 16.0], [20, 20]);
+        let chunks: Vec<_> = cam
+            .visible_chunks(&t, UVec2::splat(16))
+            .map(|c| c.to_array())
+            .collect();
+        assert_eq!(vec![[0, 0], [1, 0], [0, 1], [1, 1]], chunks);
+
+        let (t, cam) = make_pixel_cam([0.0, 0.0], [20, 20]);
+        let chunks: Vec<_> = cam
+            .visible_chunks(&t, UVec2::splat(16))
+            .map(|c| c.to_array())
+            .collect();
+        assert_eq!(vec![[-1, -1], [0, -1], [-1, 0], [0, 0]], chunks);
+
+        let (t, cam) = unit_cam([8.0, 8.0], [16, 16]);
+        assert_eq!(1, cam.visible_chunks(&t, UVec2::splat(16)).count());
+    }
+
+    #[test]
+    fn viewport_uv_to_world() {
+        let (t, cam) = unit_cam([2.0, 1.0], [8, 6]);
+        let uv = |x, y| cam.viewport_uv_to_world(Vec2::new(x, y), &t).to_array();
+        assert_eq!([2.0, 1.0], uv(0.5, 0.5));
+        assert_eq!([-2.0, -2.0], uv(0.0, 0.0));
+        assert_eq!([6.0, 4.0], uv(1.0, 1.0));
+        assert_eq!([0.0, 2.5], uv(0.25, 0.75));
+    }
+
+    #[test]
+    #[cfg(feature = "startup_log")]
+    fn startup_log() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        cam.vp_size = UVec2::new(160, 160);
+        assert!(startup_log_message(&cam)
+            .unwrap()
+            .contains("vp_size: [160, 160]"));
+        update_viewport(&mut cam, UVec2::new(200, 160), &mut proj, &mut camera);
+        assert_eq!(None, startup_log_message(&cam));
+        update_viewport(&mut cam, UVec2::new(400, 320), &mut proj, &mut camera);
+        assert_eq!(None, startup_log_message(&cam));
+    }
+
+    #[test]
+    fn frame_points() {
+        let points = [Vec2::new(-6.0, 2.0), Vec2::new(10.0, -3.0)];
+        let mut transform = Transform::default();
+
+        let mut cam = TiledCamera::unit_cam([8, 8], [8, 8]);
+        cam.frame_points(&points, 1, &mut transform, false);
+        assert_eq!([2.0, -0.5], transform.translation.truncate().to_array());
+        assert_eq!([8, 8], cam.tile_count.to_array());
+
+        cam.frame_points(&points, 1, &mut transform, true);
+        assert_eq!([18, 8], cam.tile_count.to_array());
+        let bounds = cam.visible_world_rect(&GlobalTransform::from(transform));
+        for p in points {
+            assert!(bounds.contains(p));
+            assert!(bounds.min.cmple(p - 1.0).all() && bounds.max.cmpge(p + 1.0).all());
+        }
+    }
+
+    #[test]
+    fn snap_granularity() {
+        let mut cam = TiledCamera::pixel_cam([10, 10], [8, 8]);
+        let p = Vec2::new(13.4, -5.6);
+        let mut snap = |granularity| {
+            cam.snap_granularity = granularity;
+            cam.snap_to_pixel(p).to_array()
+        };
+        assert_eq!([13.0, -6.0], snap(SnapGranularity::Pixel));
+        assert_eq!([12.0, -6.0], snap(SnapGranularity::Pixels(3)));
+        assert_eq!([16.0, -8.0], snap(SnapGranularity::Tile));
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [4, 8]);
+        let p = Vec2::new(1.3, -0.7);
+        let mut snap = |granularity| {
+            cam.snap_granularity = granularity;
+            cam.snap_to_pixel(p).to_array()
+        };
+        assert_eq!([1.25, -0.75], snap(SnapGranularity::Pixel));
+        assert_eq!([1.5, -0.5], snap(SnapGranularity::Pixels(4)));
+        assert_eq!([1.5, -1.0], snap(SnapGranularity::Tile));
+    }
+
+    #[test]
+    fn pixel_snap_system() {
+        let mut app = App::new();
+        app.add_systems(Update, update_render_transform);
+        let entity = app
+            .world
+            .spawn((
+                TiledCamera::pixel_cam([10, 10], [8, 8]),
+                PixelSnap,
+                Transform::from_xyz(3.3, 4.7, 0.0),
+                GlobalTransform::default(),
+            ))
+            .id();
+        app.update();
+        let cam = app.world.get::<TiledCamera>(entity).unwrap();
+        let global = app.world.get::<GlobalTransform>(entity).unwrap();
+        assert_eq!([3.3, 4.7], cam.logical_position().to_array());
+        assert_eq!([3.0, 5.0, 0.0], global.translation().to_array());
+    }
+
+    #[test]
+    fn integer_unit_position() {
+        let mut app = App::new();
+        app.add_systems(Update, update_render_transform);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.integer_unit_position = true;
+        let entity = app
+            .world
+            .spawn((cam, Transform::default(), GlobalTransform::default()))
+            .id();
+
+        let mut rendered = Vec::new();
+        for _ in 0..5 {
+            app.world
+                .get_mut::<Transform>(entity)
+                .unwrap()
+                .translation
+                .x += 0.3;
+            app.update();
+            rendered.push(
+                app.world
+                    .get::<GlobalTransform>(entity)
+                    .unwrap()
+                    .translation()
+                    .x,
+            );
+        }
+        assert_eq!(vec![0.0, 1.0, 1.0, 1.0, 2.0], rendered);
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert!((transform.translation.x - 1.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cursor_event_to_world() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+        let window = Window {
+            resolution: WindowResolution::new(200.0, 160.0),
+            ..default()
+        };
+
+        let screen_pos = cursor_to_screen(Vec2::new(108.0, 72.0), &window);
+        assert_eq!([108.0, 88.0], screen_pos.to_array());
+        let world = cam.screen_to_world_proj(screen_pos, proj, &t).unwrap();
+        assert_eq!([5.5, 5.5], world.to_array());
+
+        // Top left of the window is the bottom left of the screen flipped
+        let screen_pos = cursor_to_screen(Vec2::new(20.0, 0.0), &window);
+        assert_eq!([20.0, 160.0], screen_pos.to_array());
+    }
+
+    #[test]
+    fn design_scale() {
+        let cam = min_tiles_viewport([80, 48]);
+        assert_eq!(Some(UVec2::new(80, 48)), cam.design_resolution);
+        assert_eq!(Vec2::ONE, cam.design_scale());
+
+        // 2x zoom, showing 120x48 game pixels
+        let cam = min_tiles_viewport([240, 96]);
+        assert_eq!([120.0, 48.0], cam.effective_resolution().to_array());
+        assert_eq!([1.5, 1.0], cam.design_scale().to_array());
+    }
+
+    #[test]
+    fn border_tile_iter() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        for [w, h] in [[10, 6], [5, 3], [2, 2], [1, 4], [3, 1], [1, 1]] {
+            let cam = TiledCamera::unit_cam([w, h], [8, 8]);
+            let border: Vec<_> = cam.border_tile_iter(&t).collect();
+            let expected = if w == 1 || h == 1 {
+                w * h
+            } else {
+                2 * (w + h) - 4
+            };
+            assert_eq!(expected as usize, border.len(), "{}x{}", w, h);
+
+            let mut indices: Vec<_> = border.iter().map(|(i, _)| i.to_array()).collect();
+            indices.sort();
+            indices.dedup();
+            assert_eq!(border.len(), indices.len());
+        }
+
+        let cam = TiledCamera::unit_cam([4, 4], [8, 8]);
+        let mut border = cam.border_tile_iter(&t);
+        assert_eq!(
+            Some((IVec2::new(-2, -2), Vec2::new(3.5, 3.5))),
+            border.next()
+        );
+        assert!(border.all(|(i, p)| cam.world_to_index(&t, p) == i));
+    }
+
+    #[test]
+    fn rect_visibility() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let vis = |min: [f32; 2], max: [f32; 2]| {
+            cam.rect_visibility(&t, &Rect::from_corners(min.into(), max.into()))
+        };
+        assert_eq!(RectVisibility::Fully, vis([1.0, 1.0], [3.0, 3.0]));
+        assert_eq!(RectVisibility::Fully, vis([0.0, 0.0], [10.0, 10.0]));
+        assert_eq!(RectVisibility::Partial, vis([-2.0, -2.0], [1.0, 1.0]));
+        assert_eq!(RectVisibility::Partial, vis([-2.0, -2.0], [12.0, 12.0]));
+        assert_eq!(RectVisibility::None, vis([11.0, 0.0], [12.0, 3.0]));
+        assert_eq!(RectVisibility::None, vis([-3.0, 0.0], [0.0, 3.0]));
+    }
+
+    #[test]
+    fn scroll_by_tiles() {
+        let cam = TiledCamera::unit_cam([10, 10], [8, 16]);
+        assert_eq!(Vec2::new(0.5, 0.0), cam.scroll_by_tiles(IVec2::new(1, 0)));
+        assert_eq!(Vec2::new(0.0, 1.0), cam.scroll_by_tiles(IVec2::new(0, 1)));
+        let cam = TiledCamera::pixel_cam([10, 10], [8, 16]);
+        assert_eq!(Vec2::new(8.0, 0.0), cam.scroll_by_tiles(IVec2::new(1, 0)));
+        assert_eq!(
+            Vec2::new(-16.0, 32.0),
+            cam.scroll_by_tiles(IVec2::new(-2, 2))
+        );
+    }
+
+    #[test]
+    fn min_zoom_policy() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.min_zoom = 2;
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+
+        update_viewport(&mut cam, [60, 40].into(), &mut proj, &mut camera);
+        assert_eq!(2, cam.zoom());
+        assert_eq!([60, 40], cam.viewport_size().to_array());
+        assert_eq!([0, 0], cam.viewport_pos().to_array());
+        assert_eq!([10, 10], cam.world_grid().tile_count.to_array());
+
+        cam.min_zoom_policy = MinZoomPolicy::ReduceTiles;
+        update_viewport(&mut cam, [60, 40].into(), &mut proj, &mut camera);
+        assert_eq!(2, cam.zoom());
+        assert_eq!([48, 32], cam.viewport_size().to_array());
+        assert_eq!([6, 4], cam.viewport_pos().to_array());
+        assert_eq!([3, 2], cam.world_grid().tile_count.to_array());
+        assert_eq!([3.0, 2.0], cam.visible_world_size().to_array());
+        assert!(matches!(proj.scaling_mode, ScalingMode::FixedVertical(h) if h == 2.0));
+
+        // Large enough to fit - nothing is reduced
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert_eq!([10, 10], cam.world_grid().tile_count.to_array());
+    }
+
+    #[test]
+    fn visible_aspect() {
+        let cam = TiledCamera::unit_cam([16, 8], [8, 8]);
+        assert_eq!(2.0, cam.visible_aspect());
+        let cam = TiledCamera::pixel_cam([10, 10], [16, 8]);
+        assert_eq!(2.0, cam.visible_aspect());
+
+        for wres in [[80, 48], [250, 130], [1920, 1080], [1000, 1000]] {
+            let cam = min_tiles_viewport(wres);
+            let size = cam.visible_world_size();
+            assert_eq!(size.x / size.y, cam.visible_aspect());
+            let vp = cam.viewport_size().as_vec2();
+            assert!((vp.x / vp.y - cam.visible_aspect()).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn screenshot_image_size() {
+        let cam = TiledCamera::pixel_cam([20, 12], [8, 16]);
+        assert_eq!(UVec2::new(160, 192), cam.screenshot_image_size());
+        assert_eq!(
+            cam.pixels_per_tile * cam.tile_count,
+            cam.screenshot_image_size()
+        );
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        projection(&mut cam, [200, 160]);
+        assert_eq!(UVec2::new(80, 80), cam.screenshot_image_size());
+
+        // The viewport is cropped to a window smaller than the target resolution.
+        projection(&mut cam, [40, 60]);
+        assert_eq!(UVec2::new(40, 60), cam.screenshot_image_size());
+
+        // 2x zoom showing extra tiles.
+        let cam = min_tiles_viewport([240, 96]);
+        assert_eq!(UVec2::new(120, 48), cam.screenshot_image_size());
+    }
+
+    #[test]
+    fn virtual_cursor_to_world() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+        assert_eq!(
+            [5.0, 5.0],
+            ndc_to_world_proj(Vec2::ZERO, proj, &t).to_array()
+        );
+        assert_eq!(
+            [0.0, 0.0],
+            ndc_to_world_proj(-Vec2::ONE, proj, &t).to_array()
+        );
+        assert_eq!(
+            [10.0, 10.0],
+            ndc_to_world_proj(Vec2::ONE, proj, &t).to_array()
+        );
+
+        let mut cam = TiledCamera::pixel_cam([10, 10], [8, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+        for ndc in [[0.25, -0.5], [-1.0, 1.0], [0.9, 0.1]] {
+            let ndc = Vec2::from(ndc);
+            let world = ndc_to_world_proj(ndc, proj, &t);
+            let round_trip = world_to_ndc_proj(world, proj, &t).truncate();
+            assert!((round_trip - ndc).abs().max_element() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn follow_max_lag() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let target = app
+            .world
+            .spawn(GlobalTransform::from_xyz(100.0, 0.0, 0.0))
+            .id();
+        let cam = spawn_cam(&mut app, TiledCamera::unit_cam([10, 10], [8, 8]));
+        let mut follow = TiledCameraFollow::new(target);
+        follow.follow_lerp = Some(1.0);
+        app.world.entity_mut(cam).insert(follow);
+
+        let step = |app: &mut App| {
+            let delta = std::time::Duration::from_millis(100);
+            app.world.resource_mut::<Time>().advance_by(delta);
+            app.update();
+            app.world
+                .get::<Transform>(cam)
+                .unwrap()
+                .translation
+                .truncate()
+        };
+
+        // Smoothing only - the camera falls far behind
+        assert_eq!([10.0, 0.0], step(&mut app).to_array());
+
+        follow.max_lag_tiles = Some(2.0);
+        app.world.entity_mut(cam).insert(follow);
+        assert_eq!([98.0, 0.0], step(&mut app).to_array());
+
+        // Within the lag limit smoothing works as normal
+        *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(99.0, 0.0, 0.0);
+        let pos = step(&mut app);
+        assert!((pos.x - 98.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn scale_axis() {
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        let mut update = |axis: ScaleAxis, wres: [u32; 2]| {
+            let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+            cam.scale_axis = axis;
+            update_viewport(&mut cam, wres.into(), &mut proj, &mut camera);
+            let horizontal = match proj.scaling_mode {
+                ScalingMode::FixedHorizontal(w) => {
+                    assert_eq!(10.0, w);
+                    true
+                }
+                ScalingMode::FixedVertical(h) => {
+                    assert_eq!(10.0, h);
+                    false
+                }
+                _ => panic!(),
+            };
+            let vp = camera.viewport.clone().unwrap();
+            assert!((vp.physical_position + vp.physical_size)
+                .cmple(wres.into())
+                .all());
+            (cam.zoom(), horizontal)
+        };
+
+        assert_eq!((2, false), update(ScaleAxis::Auto, [250, 170]));
+        assert_eq!((2, true), update(ScaleAxis::Auto, [170, 250]));
+        assert_eq!((2, false), update(ScaleAxis::Vertical, [250, 170]));
+        assert_eq!((3, true), update(ScaleAxis::Horizontal, [250, 170]));
+        assert_eq!((3, false), update(ScaleAxis::Vertical, [170, 250]));
+    }
+
+    #[test]
+    fn scale_axis_crops_other_axis() {
+        let mut cam = TiledCamera::unit_cam([20, 10], [16, 18]);
+        cam.scale_axis = ScaleAxis::Vertical;
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(&mut cam, [1024, 768].into(), &mut proj, &mut camera);
+
+        // 1280x720 at zoom 4, cropped horizontally to the window.
+        assert_eq!(4, cam.zoom());
+        assert_eq!([1024, 720], cam.viewport_size().to_array());
+        assert_eq!([0, 24], cam.viewport_pos().to_array());
+        assert!(matches!(proj.scaling_mode, ScalingMode::FixedVertical(h) if h == 10.0));
+        // The projection only covers the visible 16 of 20 horizontal tiles.
+        let size = cam.visible_world_size();
+        assert!((size.x - 16.0 * 16.0 / 18.0).abs() < 1e-4);
+        assert_eq!(10.0, size.y);
+
+        cam.scale_axis = ScaleAxis::Horizontal;
+        update_viewport(&mut cam, [1300, 600].into(), &mut proj, &mut camera);
+        assert_eq!(4, cam.zoom());
+        assert_eq!([1280, 600], cam.viewport_size().to_array());
+        assert_eq!([10, 0], cam.viewport_pos().to_array());
+        assert!(
+            matches!(proj.scaling_mode, ScalingMode::FixedHorizontal(w) if (w - 20.0 * 16.0 / 18.0).abs() < 1e-4)
+        );
+    }
+
+    #[test]
+    fn cell_size_world() {
+        for (ppt, units, pixels) in [
+            ([8, 8], [1.0, 1.0], [8.0, 8.0]),
+            ([8, 16], [0.5, 1.0], [8.0, 16.0]),
+            ([24, 12], [2.0, 1.0], [24.0, 12.0]),
+        ] {
+            let unit = TiledCamera::unit_cam([10, 10], ppt);
+            let pixel = TiledCamera::pixel_cam([10, 10], ppt);
+            assert_eq!(units, unit.cell_size_world().to_array());
+            assert_eq!(pixels, pixel.cell_size_world().to_array());
+
+            for cam in [unit, pixel] {
+                let cell = cam.cell_size_world();
+                assert_eq!(cam.world_grid().tile_size_world(), cell);
+                assert_eq!(
+                    Some(cell).filter(|_| cam.world_space() == WorldSpace::Units),
+                    cam.unit_size()
+                );
+                assert_eq!(cell, cam.scroll_by_tiles(IVec2::ONE));
+                let mut cam = cam;
+                cam.snap_granularity = SnapGranularity::Tile;
+                assert_eq!(cell, cam.snap_to_pixel(cell * 0.6));
+                cam.snap_granularity = SnapGranularity::Pixel;
+                let pixel = cell / UVec2::from(ppt).as_vec2();
+                assert_eq!(pixel, cam.snap_to_pixel(pixel * 0.6));
+            }
+        }
+    }
+
+    #[test]
+    fn camera_anchor() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.camera_anchor = Vec2::ZERO;
+        let proj = projection(&mut cam, [200, 160]);
+
+        let screen = cam.world_to_screen_proj(Vec2::new(5.0, 5.0), proj, &t);
+        assert_eq!(Some(Vec2::new(20.0, 0.0)), screen);
+        let world = cam.screen_to_world_proj(Vec2::new(20.0, 0.0), proj, &t);
+        assert_eq!(Some(Vec2::new(5.0, 5.0)), world);
+
+        assert_eq!([5.0, 5.0], cam.visible_world_rect(&t).min.to_array());
+        assert_eq!([-5, -5], cam.world_to_index(&t, [5.5, 5.5]).to_array());
+        assert_eq!(Some(Vec2::new(5.5, 5.5)), cam.tile_center_iter(&t).next());
+
+        let mut transform = Transform::default();
+        cam.frame_point_with_tiles(Vec2::new(10.0, 10.0), UVec2::new(10, 10), &mut transform);
+        assert_eq!([5.0, 5.0], transform.translation.truncate().to_array());
+
+        cam.bounds = Some(Rect::new(0.0, 0.0, 20.0, 20.0));
+        assert_eq!(Some(Rect::new(0.0, 0.0, 10.0, 10.0)), cam.pan_limits());
+    }
+
+    #[test]
+    fn secondary_window() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let window = Window {
+            resolution: WindowResolution::new(100.0, 100.0),
+            ..default()
+        };
+        let window = app.world.spawn(window).id();
+        let primary = app.world.spawn(TiledCameraBundle::unit_cam([10, 10])).id();
+        let secondary = app
+            .world
+            .spawn(TiledCameraBundle::unit_cam([10, 10]).with_window(window))
+            .id();
+        let missing = app
+            .world
+            .spawn(TiledCameraBundle::unit_cam([10, 10]).with_window(Entity::PLACEHOLDER))
+            .id();
+        app.update();
+
+        let vp_size =
+            |app: &App, e: Entity| app.world.get::<TiledCamera>(e).unwrap().viewport_size();
+        assert_eq!([160, 160], vp_size(&app, primary).to_array());
+        assert_eq!([80, 80], vp_size(&app, secondary).to_array());
+        assert!(!app
+            .world
+            .get::<TiledCamera>(missing)
+            .unwrap()
+            .is_initialized());
+
+        app.world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .resolution
+            .set(170.0, 170.0);
+        app.world.send_event(WindowResized {
+            window,
+            width: 170.0,
+            height: 170.0,
+        });
+        app.update();
+        assert_eq!([160, 160], vp_size(&app, primary).to_array());
+        assert_eq!([160, 160], vp_size(&app, secondary).to_array());
+    }
+
+    #[test]
+    fn pixel_snap_world() {
+        let mut cam = TiledCamera::pixel_cam([10, 10], [8, 8]);
+        assert_eq!(
+            [3.0, -2.0],
+            cam.pixel_snap_world(Vec2::new(3.4, -1.6)).to_array()
+        );
+        cam.snap_granularity = SnapGranularity::Tile;
+        assert_eq!(
+            [0.0, -8.0],
+            cam.pixel_snap_world(Vec2::new(3.4, -4.1)).to_array()
+        );
+        assert_eq!(
+            [16.0, 8.0],
+            cam.pixel_snap_world(Vec2::new(12.5, 5.0)).to_array()
+        );
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [4, 4]);
+        assert_eq!(
+            [1.25, 0.5],
+            cam.pixel_snap_world(Vec2::new(1.3, 0.6)).to_array()
+        );
+        cam.snap_granularity = SnapGranularity::Tile;
+        assert_eq!(
+            [1.0, 1.0],
+            cam.pixel_snap_world(Vec2::new(1.3, 0.6)).to_array()
+        );
+    }
+
+    #[test]
+    fn letterbox_color() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let color = Color::rgb(1.0, 0.0, 0.0);
+        let cam = app
+            .world
+            .spawn(TiledCameraBundle::unit_cam([10, 10]).with_letterbox_color(color))
+            .id();
+        app.update();
+
+        let bars = |app: &mut App| {
+            let mut q = app
+                .world
+                .query::<(Entity, &Letterbox, &Sprite, &Transform, &Visibility)>();
+            let mut bars: Vec<_> = q
+                .iter(&app.world)
+                .filter(|(.., v)| **v != Visibility::Hidden)
+                .map(|(e, lb, s, t, _)| (e, lb.owner, s.custom_size, t.translation.x))
+                .collect();
+            bars.sort_by(|a, b| a.3.total_cmp(&b.3));
+            bars
+        };
+        let first = bars(&mut app);
+        assert_eq!(
+            vec![
+                (cam, Some(Vec2::new(20.0, 160.0)), -90.0),
+                (cam, Some(Vec2::new(20.0, 160.0)), 90.0)
+            ],
+            first.iter().map(|b| (b.1, b.2, b.3)).collect::<Vec<_>>()
+        );
+        let mut q = app.world.query::<(Entity, &Letterbox, &Camera)>();
+        let (letterbox_cam, _, camera) = q.single(&app.world);
+        assert_eq!(LETTERBOX_ORDER, camera.order);
+
+        // Changes update the existing entities in place
+        let (window, mut win) = app
+            .world
+            .query_filtered::<(Entity, &mut Window), With<PrimaryWindow>>()
+            .single_mut(&mut app.world);
+        win.resolution.set(240.0, 160.0);
+        app.world.send_event(WindowResized {
+            window,
+            width: 240.0,
+            height: 160.0,
+        });
+        app.update();
+        let resized = bars(&mut app);
+        assert_eq!(
+            vec![first[0].0, first[1].0],
+            vec![resized[0].0, resized[1].0]
+        );
+        assert_eq!(Some(Vec2::new(40.0, 160.0)), resized[0].2);
+        assert_eq!(letterbox_cam, q.single(&app.world).0);
+
+        // Removing the color removes the bars
+        app.world
+            .get_mut::<TiledCamera>(cam)
+            .unwrap()
+            .letterbox_color = None;
+        app.update();
+        let mut q = app.world.query::<&Letterbox>();
+        assert_eq!(0, q.iter(&app.world).count());
+    }
+
+    #[test]
+    fn follow_bounds() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let target = app
+            .world
+            .spawn(GlobalTransform::from_xyz(4.0, 60.0, 0.0))
+            .id();
+        let cam = spawn_cam(&mut app, TiledCamera::pixel_cam([10, 10], [8, 8]));
+        let mut follow = TiledCameraFollow::new(target);
+        follow.bounds = Some(Rect::new(0.0, 0.0, 160.0, 100.0));
+        app.world.entity_mut(cam).insert(follow);
+        app.update();
+
+        // The view is 80x80 world units
+        let pos = app.world.get::<Transform>(cam).unwrap().translation;
+        assert_eq!([40.0, 60.0], pos.truncate().to_array());
+
+        *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(500.0, -20.0, 0.0);
+        app.update();
+        let pos = app.world.get::<Transform>(cam).unwrap().translation;
+        assert_eq!([120.0, 40.0], pos.truncate().to_array());
+
+        // Units
+        app.world
+            .get_mut::<TiledCamera>(cam)
+            .unwrap()
+            .set_world_space(WorldSpace::Units);
+        follow.bounds = Some(Rect::new(0.0, 0.0, 20.0, 10.0));
+        app.world.entity_mut(cam).insert(follow);
+        app.update();
+        let pos = app.world.get::<Transform>(cam).unwrap().translation;
+        assert_eq!([15.0, 5.0], pos.truncate().to_array());
+    }
+
+    #[test]
+    fn compute_viewport_for() {
+        let cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let vp = |size: [u32; 2]| {
+            let (pos, size, zoom) = cam.compute_viewport_for(size.into());
+            (pos.to_array(), size.to_array(), zoom)
+        };
+        assert_eq!(([0, 0], [80, 80], 1), vp([80, 80]));
+        assert_eq!(([60, 20], [160, 160], 2), vp([280, 200]));
+        assert_eq!(([0, 10], [50, 80], 1), vp([50, 100]));
+        assert_eq!(([0, 0], [1, 1], 1), vp([1, 1]));
+        assert_eq!(([440, 20], [1040, 1040], 13), vp([1920, 1080]));
+
+        // Matches the live viewport update without modifying the camera
+        let mut live = TiledCamera::unit_cam([10, 10], [8, 8]);
+        projection(&mut live, [280, 200]);
+        assert_eq!(
+            (live.viewport_pos(), live.viewport_size(), live.zoom()),
+            cam.compute_viewport_for([280, 200].into())
+        );
+        assert!(!cam.is_initialized());
+    }
+
+    #[test]
+    fn initialized_event() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let a = app.world.spawn(TiledCameraBundle::unit_cam([10, 10])).id();
+        let b = app.world.spawn(TiledCameraBundle::unit_cam([5, 5])).id();
+
+        let mut reader = app
+            .world
+            .resource::<Events<TiledCameraInitialized>>()
+            .get_reader();
+        let mut sent = Vec::new();
+        for _ in 0..3 {
+            app.update();
+            app.world.get_mut::<TiledCamera>(a).unwrap().tile_count = UVec2::new(8, 8);
+            let events = app.world.resource::<Events<TiledCameraInitialized>>();
+            sent.extend(reader.read(events).copied());
+        }
+        sent.sort_by_key(|e| e.entity);
+        assert_eq!(
+            vec![
+                TiledCameraInitialized {
+                    entity: a,
+                    vp_size: UVec2::new(160, 160),
+                    zoom: 2,
+                },
+                TiledCameraInitialized {
+                    entity: b,
+                    vp_size: UVec2::new(160, 160),
+                    zoom: 4,
+                },
+            ],
+            sent
+        );
+    }
+
+    #[test]
+    fn visible_world_rect() {
+        let (t, cam) = unit_cam([3.5, -2.0], [10, 6]);
+        assert_eq!(Rect::new(-1.5, -5.0, 8.5, 1.0), cam.visible_world_rect(&t));
+
+        let (t, cam) = make_pixel_cam([12.0, -40.0], [10, 6]);
+        assert_eq!(
+            Rect::new(-28.0, -64.0, 52.0, -16.0),
+            cam.visible_world_rect(&t)
+        );
+    }
+
+    #[test]
+    fn tiles_along_line() {
+        let (t, cam) = unit_cam([0.0, 0.0], [10, 10]);
+        let line = |a: [f32; 2], b: [f32; 2]| -> Vec<[i32; 2]> {
+            cam.tiles_along_line(&t, a.into(), b.into())
+                .map(|i| i.to_array())
+                .collect()
+        };
+
+        assert_eq!(
+            vec![[0, 0], [1, 0], [2, 0], [3, 0]],
+            line([0.5, 0.5], [3.5, 0.2])
+        );
+        assert_eq!(
+            vec![[-1, 2], [-1, 1], [-1, 0]],
+            line([-0.5, 2.9], [-0.1, 0.1])
+        );
+        assert_eq!(vec![[0, 0], [1, 1], [2, 2]], line([0.5, 0.5], [2.5, 2.5]));
+        assert_eq!(
+            vec![[0, 0], [1, 0], [1, 1], [2, 1]],
+            line([0.1, 0.2], [2.9, 1.8])
+        );
+        assert_eq!(vec![[-2, -3]], line([-1.5, -2.5], [-1.2, -2.9]));
+
+        // Odd tile counts shift tile boundaries by half a tile
+        let (t, cam) = make_pixel_cam([0.0, 0.0], [5, 5]);
+        let start = Vec2::new(-3.0, 0.0);
+        let tiles: Vec<_> = cam
+            .tiles_along_line(&t, start, Vec2::new(13.0, 0.0))
+            .collect();
+        assert_eq!(cam.world_to_index(&t, start), tiles[0]);
+        assert_eq!(
+            vec![IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(2, 0)],
+            tiles
+        );
+    }
+
+    #[test]
+    fn world_to_index_checked() {
+        // Even: indices -2..=1, tiles span -2.0..2.0
+        let (t, cam) = unit_cam([0.0, 0.0], [4, 4]);
+        assert_eq!(
+            Some(IVec2::new(-2, -2)),
+            cam.world_to_index_checked(&t, [-1.9, -1.9])
+        );
+        assert_eq!(
+            Some(IVec2::new(1, 1)),
+            cam.world_to_index_checked(&t, [1.9, 1.9])
+        );
+        assert_eq!(None, cam.world_to_index_checked(&t, [2.1, 0.0]));
+        assert_eq!(None, cam.world_to_index_checked(&t, [0.0, -2.1]));
+
+        // Odd: indices -2..=2, tiles span -2.5..2.5
+        let (t, cam) = unit_cam([10.0, 0.0], [5, 5]);
+        assert_eq!(
+            Some(IVec2::new(2, -2)),
+            cam.world_to_index_checked(&t, [12.4, -2.4])
+        );
+        assert_eq!(
+            Some(IVec2::new(-2, 0)),
+            cam.world_to_index_checked(&t, [7.6, 0.0])
+        );
+        assert_eq!(None, cam.world_to_index_checked(&t, [12.6, 0.0]));
+        assert_eq!(None, cam.world_to_index_checked(&t, [7.4, 0.0]));
+    }
+
+    #[test]
+    fn world_space_transition_scale() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        assert_eq!(Vec2::splat(8.0), cam.world_space_transition_scale());
+        let mut transform = Transform::from_xyz(3.0, -2.0, 0.0);
+        let point = Vec2::new(4.5, -1.0);
+
+        let proj = projection(&mut cam, [200, 160]);
+        let before = cam.world_to_screen_proj(point, proj, &GlobalTransform::from(transform));
+
+        let scale = cam.world_space_transition_scale();
+        cam.set_world_space_preserving_view(WorldSpace::Pixels, &mut transform);
+        assert_eq!([24.0, -16.0], transform.translation.truncate().to_array());
+        let proj = projection(&mut cam, [200, 160]);
+        let after =
+            cam.world_to_screen_proj(point * scale, proj, &GlobalTransform::from(transform));
+        assert_eq!(before, after);
+
+        assert_eq!(Vec2::splat(1.0 / 8.0), cam.world_space_transition_scale());
+        cam.set_world_space_preserving_view(WorldSpace::Units, &mut transform);
+        assert_eq!([3.0, -2.0], transform.translation.truncate().to_array());
+    }
+
+    #[test]
+    fn integer_scaling() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(&mut cam, [300, 200].into(), &mut proj, &mut camera);
+        assert_eq!(2, cam.zoom());
+        assert_eq!(2.0, cam.zoom_f32());
+        assert_eq!([160, 160], cam.viewport_size().to_array());
+
+        cam.integer_scaling = false;
+        update_viewport(&mut cam, [300, 200].into(), &mut proj, &mut camera);
+        assert_eq!(2, cam.zoom());
+        assert_eq!(2.5, cam.zoom_f32());
+        assert_eq!([200, 200], cam.viewport_size().to_array());
+        assert_eq!([50, 0], cam.viewport_pos().to_array());
+        assert_eq!([20, 20], cam.tile_render_size().to_array());
+        assert_eq!([10.0, 10.0], cam.visible_world_size().to_array());
+
+        cam.responsive_mode = ResponsiveMode::MinTiles;
+        update_viewport(&mut cam, [300, 200].into(), &mut proj, &mut camera);
+        assert_eq!([300, 200], cam.viewport_size().to_array());
+        assert_eq!([15.0, 10.0], cam.visible_world_size().to_array());
+    }
+
+    #[test]
+    fn resized_event() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let cam = app.world.spawn(TiledCameraBundle::unit_cam([10, 10])).id();
+        let mut reader = app
+            .world
+            .resource::<Events<TiledCameraResized>>()
+            .get_reader();
+        let mut step = |app: &mut App| {
+            app.update();
+            let events = app.world.resource::<Events<TiledCameraResized>>();
+            reader.read(events).copied().collect::<Vec<_>>()
+        };
+
+        let expected = TiledCameraResized {
+            entity: cam,
+            zoom: 2,
+            vp_size: UVec2::new(160, 160),
+            vp_pos: UVec2::new(20, 0),
+        };
+        assert_eq!(vec![expected], step(&mut app));
+
+        // Changed, but the viewport is the same
+        app.world.get_mut::<TiledCamera>(cam).unwrap().render_offset = Vec2::ONE;
+        assert!(step(&mut app).is_empty());
+
+        app.world.get_mut::<TiledCamera>(cam).unwrap().tile_count = UVec2::new(5, 5);
+        let expected = TiledCameraResized {
+            zoom: 4,
+            ..expected
+        };
+        assert_eq!(vec![expected], step(&mut app));
+    }
+
+    #[test]
+    fn zoom_changed_event() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let window = app
+            .world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(&app.world);
+        let cam = app.world.spawn(TiledCameraBundle::unit_cam([10, 10])).id();
+        let mut reader = app.world.resource::<Events<ZoomChanged>>().get_reader();
+        let mut step = |app: &mut App, wres: Option<[f32; 2]>| {
+            if let Some([w, h]) = wres {
+                let mut win = app.world.get_mut::<Window>(window).unwrap();
+                win.resolution.set(w, h);
+                app.world.send_event(WindowResized {
+                    window,
+                    width: w,
+                    height: h,
+                });
+            }
+            app.update();
+            let events = app.world.resource::<Events<ZoomChanged>>();
+            reader.read(events).copied().collect::<Vec<_>>()
+        };
+
+        // Initialization isn't a zoom change
+        assert!(step(&mut app, None).is_empty());
+        // Resized, but still 2x
+        assert!(step(&mut app, Some([220.0, 170.0])).is_empty());
+
+        let expected = ZoomChanged {
+            entity: cam,
+            old: 2,
+            new: 3,
+        };
+        assert_eq!(vec![expected], step(&mut app, Some([260.0, 250.0])));
+        assert!(step(&mut app, None).is_empty());
+    }
+
+    #[test]
+    fn depth_range() {
+        let bundle = TiledCameraBundle::new();
+        assert_eq!(-1000.0, bundle.cam2d_bundle.projection.near);
+        assert_eq!(1000.0, bundle.cam2d_bundle.projection.far);
+
+        let bundle = TiledCameraBundle::new()
+            .with_camera_position([3.0, 4.0])
+            .with_depth_range(-10.0, 5000.0);
+        assert_eq!(-10.0, bundle.cam2d_bundle.projection.near);
+        assert_eq!(5000.0, bundle.cam2d_bundle.projection.far);
+        assert_eq!(
+            [3.0, 4.0, 0.0],
+            bundle.cam2d_bundle.transform.translation.to_array()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn depth_range_invalid() {
+        TiledCameraBundle::new().with_depth_range(10.0, 10.0);
+    }
+
+    #[test]
+    fn row_column_centers() {
+        let (t, cam) = unit_cam([2.0, 1.0], [5, 4]);
+        let row = cam.row_centers_world(&t, -2).unwrap();
+        assert_eq!(5, row.len());
+        assert!(row.iter().all(|p| p.y == -0.5));
+        assert_eq!([0.0, -0.5], row[0].to_array());
+        assert_eq!([4.0, -0.5], row[4].to_array());
+        assert_eq!(None, cam.row_centers_world(&t, 2));
+
+        let column = cam.column_centers_world(&t, 2).unwrap();
+        assert_eq!(4, column.len());
+        assert!(column.iter().all(|p| p.x == 4.0));
+        assert_eq!(None, cam.column_centers_world(&t, -3));
+    }
+
+    #[test]
+    fn safe_zone() {
+        let t = GlobalTransform::from_xyz(5.0, 0.0, 0.0);
+        let aspects = [16.0 / 9.0, 4.0 / 3.0, 21.0 / 9.0];
+
+        // A fixed view is the same for every aspect
+        let mut cam = TiledCamera::unit_cam([16, 9], [8, 8]);
+        projection(&mut cam, [256, 144]);
+        for rect in cam.aspect_view_rects(&t, &aspects) {
+            assert_eq!(cam.visible_world_rect(&t), rect);
+        }
+
+        cam.responsive_mode = ResponsiveMode::MinTiles;
+        projection(&mut cam, [256, 144]);
+        let rects = cam.aspect_view_rects(&t, &aspects);
+        assert_eq!([16.0, 9.0], rects[0].size().to_array());
+        assert_eq!([24.0, 18.0], rects[1].size().to_array());
+        assert_eq!([21.0, 9.0], rects[2].size().to_array());
+        assert_eq!(
+            Rect::new(-3.0, -4.5, 13.0, 4.5),
+            cam.safe_zone(&t, &aspects)
+        );
+    }
+
+    #[test]
+    fn tile_index_iter() {
+        for count in [[4, 3], [5, 6], [1, 1]] {
+            let (t, cam) = unit_cam([3.0, -1.0], count);
+            assert_eq!(
+                (count[0] * count[1]) as usize,
+                cam.tile_index_iter().count()
+            );
+
+            let tiles: Vec<_> = cam.tile_index_pos_iter(&t).collect();
+            let (first, last) = (tiles[0], tiles[tiles.len() - 1]);
+            assert_eq!(cam.index_to_tile_pos(&t, first.0), first.1);
+            assert_eq!(cam.index_to_tile_pos(&t, last.0), last.1);
+            assert!(tiles
+                .iter()
+                .all(|(i, p)| cam.world_to_index(&t, *p + Vec2::splat(0.5)) == *i));
+        }
+    }
+
+    #[test]
+    fn degenerate_tile_count() {
+        let mut cam = TiledCamera::unit_cam([0, 5], [8, 8]);
+        assert!(!cam.is_valid());
+        assert_eq!([8, 40], cam.target_resolution().to_array());
+
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert!(cam.warned_invalid);
+        let viewport = camera.viewport.clone().unwrap();
+        assert_eq!([32, 160], viewport.physical_size.to_array());
+        assert_eq!([84, 0], viewport.physical_position.to_array());
+        assert_eq!(4, cam.zoom());
+        assert!(cam.visible_world_size().is_finite());
+
+        cam.pixels_per_tile = UVec2::ZERO;
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert!(camera
+            .viewport
+            .clone()
+            .unwrap()
+            .physical_size
+            .cmpgt(UVec2::ZERO)
+            .all());
+        assert!(cam.visible_world_size().is_finite());
+
+        cam.tile_count = UVec2::new(10, 10);
+        cam.pixels_per_tile = UVec2::new(8, 8);
+        assert!(cam.is_valid());
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert!(!cam.warned_invalid);
+    }
+
+    #[test]
+    fn screen_to_index() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let center = Vec2::new(100.0, 80.0);
+
+        for count in [10, 9] {
+            let mut cam = TiledCamera::unit_cam([count, count], [8, 8]);
+            let proj = projection(&mut cam, [200, 160]);
+            let world = cam.screen_to_world_proj(center, proj, &t).unwrap();
+            let index = cam.screen_to_index_proj(center, proj, &t);
+            assert_eq!(Some(IVec2::ZERO), index);
+            assert_eq!(Some(cam.world_to_index(&t, world)), index);
+        }
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+        assert_eq!(
+            Some(IVec2::new(-5, -5)),
+            cam.screen_to_index_proj(Vec2::new(20.0, 0.0), proj, &t)
+        );
+        // Letterbox bar
+        assert_eq!(
+            None,
+            cam.screen_to_index_proj(Vec2::new(10.0, 80.0), proj, &t)
+        );
+    }
+
+    #[test]
+    fn screen_to_world_outside_viewport() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        // Wide window, bars on the left and right
+        let proj = projection(&mut cam, [200, 160]);
+        assert_eq!([20, 0], cam.viewport_pos().to_array());
+
+        assert_eq!(
+            None,
+            cam.screen_to_world_proj(Vec2::new(10.0, 80.0), proj, &t)
+        );
+        assert_eq!(
+            None,
+            cam.screen_to_world_proj(Vec2::new(190.0, 80.0), proj, &t)
+        );
+        assert_eq!(
+            None,
+            cam.screen_to_world_proj(Vec2::new(180.0, 80.0), proj, &t)
+        );
+        assert_eq!(
+            Some(Vec2::new(0.0, 5.0)),
+            cam.screen_to_world_proj(Vec2::new(20.0, 80.0), proj, &t)
+        );
+
+        // Tall window, bars on the top and bottom
+        let proj = projection(&mut cam, [160, 200]);
+        assert_eq!([0, 20], cam.viewport_pos().to_array());
+        assert_eq!(
+            None,
+            cam.screen_to_world_proj(Vec2::new(80.0, 10.0), proj, &t)
+        );
+        assert_eq!(
+            None,
+            cam.screen_to_world_proj(Vec2::new(80.0, 190.0), proj, &t)
+        );
+        assert_eq!(
+            Some(Vec2::new(5.0, 5.0)),
+            cam.screen_to_world_proj(Vec2::new(80.0, 100.0), proj, &t)
+        );
+    }
+
+    #[test]
+    fn non_square_tiles() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let screen_pos = Vec2::new(70.0, 18.0);
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [4, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+        assert_eq!([60, 0], cam.viewport_pos().to_array());
+        assert_eq!([80, 160], cam.viewport_size().to_array());
+
+        let world = cam.screen_to_world_proj(Vec2::new(100.0, 80.0), proj, &t);
+        assert_eq!(Some(Vec2::new(5.0, 5.0)), world);
+        let world = cam.screen_to_world_proj(screen_pos, proj, &t).unwrap();
+        assert_eq!([3.125, 1.125], world.to_array());
+        let index = cam.world_to_index(&t, world);
+        assert_eq!([-4, -4], index.to_array());
+        assert_eq!([-3.75, -3.875], cam.world_to_tile(&t, world).to_array());
+        assert_eq!([3.0, 1.0], cam.index_to_tile_pos(&t, index).to_array());
+        let center = cam.index_to_tile_center(&t, index);
+        assert_eq!([3.25, 1.5], center.to_array());
+        let screen = cam.world_to_screen_proj(center, proj, &t);
+        assert_eq!(Some(Vec2::new(72.0, 24.0)), screen);
+
+        let mut cam = TiledCamera::pixel_cam([10, 10], [4, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+        let world = cam.screen_to_world_proj(screen_pos, proj, &t).unwrap();
+        assert_eq!([-10.0, -26.0], world.to_array());
+        let index = cam.world_to_index(&t, world);
+        assert_eq!([-4, -4], index.to_array());
+        assert_eq!([-3.75, -3.875], cam.world_to_tile(&t, world).to_array());
+        assert_eq!([-11.0, -27.0], cam.index_to_tile_pos(&t, index).to_array());
+        let center = cam.index_to_tile_center(&t, index);
+        assert_eq!([-9.0, -23.0], center.to_array());
+        let screen = cam.world_to_screen_proj(center, proj, &t);
+        assert_eq!(Some(Vec2::new(72.0, 24.0)), screen);
+
+        // Odd tile counts put tile 0 at the camera center.
+        let t = GlobalTransform::IDENTITY;
+        let cam = TiledCamera::unit_cam([9, 9], [4, 8]);
+        assert_eq!([0.0, 0.0], cam.index_to_tile_center(&t, [0, 0]).to_array());
+        assert_eq!([-0.25, -0.5], cam.index_to_tile_pos(&t, [0, 0]).to_array());
+        assert_eq!([0, 0], cam.world_to_index(&t, [0.2, 0.4]).to_array());
+    }
+
+    #[test]
+    fn fit_tiles_to_window() {
+        for (window, tiles, ppt) in [
+            ([1920, 1080], [16, 9], [120, 120]),
+            ([1920, 1080], [40, 20], [48, 48]),
+            ([1280, 720], [16, 16], [45, 45]),
+            ([1280, 1024], [20, 15], [64, 64]),
+            ([10, 10], [20, 20], [1, 1]),
+        ] {
+            let fit = TiledCamera::fit_tiles_to_window(window.into(), tiles.into());
+            assert_eq!(ppt, fit.to_array());
+
+            let mut cam = TiledCamera::unit_cam(tiles, fit);
+            if fit != UVec2::ONE {
+                assert!(cam.target_resolution().cmple(window.into()).all());
+                projection(&mut cam, window);
+                assert_eq!(1, cam.zoom());
+            }
+        }
     }
 
-    /// Converts a world position to a screen position (0..resolution)
-    pub fn world_to_screen(
-        &self,
-        world_pos: impl Point2d,
-        camera: &Camera,
-        camera_transform: &GlobalTransform,
-    ) -> Option<Vec2> {
-        let window_size = self.vp_size.as_vec2();
+    #[test]
+    fn set_tile_count_around() {
+        for mut cam in [
+            TiledCamera::unit_cam([10, 10], [8, 8]),
+            TiledCamera::pixel_cam([10, 10], [8, 8]),
+        ] {
+            let mut transform = Transform::from_xyz(3.0, 2.0, 0.0);
+            let point = Vec2::new(5.5, 4.25);
+            let proj = projection(&mut cam, [160, 160]);
+            assert_eq!(2, cam.zoom());
+            let t = GlobalTransform::from(transform);
+            let before = cam.world_to_screen_proj(point, proj, &t).unwrap();
 
-        // Build a transform to convert from world to NDC using camera data
-        let world_to_ndc: Mat4 =
-            camera.projection_matrix() * camera_transform.compute_matrix().inverse();
-        let ndc_space_coords: Vec3 = world_to_ndc.project_point3(world_pos.as_vec2().extend(0.0));
+            cam.set_tile_count_around(UVec2::new(20, 20), point, &mut transform);
+            assert_eq!([20, 20], cam.tile_count.to_array());
+            let proj = projection(&mut cam, [160, 160]);
+            assert_eq!(1, cam.zoom());
+            let t = GlobalTransform::from(transform);
+            let after = cam.world_to_screen_proj(point, proj, &t).unwrap();
+            assert_eq!(before, after);
+        }
+    }
 
-        // NDC z-values outside of 0 < z < 1 are outside the camera frustum and are thus not in screen space
-        if ndc_space_coords.z < 0.0 || ndc_space_coords.z > 1.0 {
-            return None;
+    #[test]
+    fn reflect() {
+        use bevy::reflect::GetPath;
+
+        let mut app = App::new();
+        app.add_plugins(TiledCameraPlugin);
+        let registry = app.world.resource::<AppTypeRegistry>().read();
+        let registration = registry.get(std::any::TypeId::of::<TiledCamera>()).unwrap();
+        assert!(registration.data::<ReflectComponent>().is_some());
+        // Every reflected field type needs to be registered for inspectors
+        // and scene serialization to handle the camera.
+        let field_types = [
+            std::any::TypeId::of::<CameraWorldSpace>(),
+            std::any::TypeId::of::<ResponsiveMode>(),
+            std::any::TypeId::of::<CenterBias>(),
+            std::any::TypeId::of::<ScaleAxis>(),
+            std::any::TypeId::of::<MinZoomPolicy>(),
+            std::any::TypeId::of::<HiDpiMode>(),
+            std::any::TypeId::of::<CameraOrigin>(),
+            std::any::TypeId::of::<SnapGranularity>(),
+            std::any::TypeId::of::<ViewportMargins>(),
+        ];
+        for type_id in field_types {
+            assert!(registry.get(type_id).is_some());
         }
+        drop(registry);
 
-        // Once in NDC space, we can discard the z element and rescale x/y to fit the screen
-        let screen_space_coords = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0 * window_size;
-        if !screen_space_coords.is_nan() {
-            Some((screen_space_coords + self.vp_pos.as_vec2()).round())
-        } else {
-            None
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        *cam.path_mut::<UVec2>("tile_count").unwrap() = UVec2::new(20, 15);
+        *cam.path_mut::<UVec2>("pixels_per_tile").unwrap() = UVec2::new(4, 4);
+        assert_eq!([20, 15], cam.tile_count.to_array());
+        assert_eq!([4, 4], cam.pixels_per_tile.to_array());
+        assert!(cam.reflect_path("vp_size").is_err());
+
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(&mut cam, [160, 120].into(), &mut proj, &mut camera);
+        assert_eq!(2, cam.zoom());
+        assert_eq!([160, 120], cam.viewport_size().to_array());
+
+        // World space is applied to the grid on the next viewport update.
+        cam.reflect_path_mut("world_space")
+            .unwrap()
+            .apply(&CameraWorldSpace::Pixels);
+        assert_eq!(WorldSpace::Pixels, cam.world_space());
+        assert_eq!(Vec2::new(4.0, 4.0), cam.cell_size_world());
+        update_viewport(&mut cam, [160, 120].into(), &mut proj, &mut camera);
+        assert_eq!(WorldSpace::Pixels, cam.world_grid().world_space);
+        assert_eq!(Vec2::new(80.0, 60.0), cam.world_grid().world_size());
+    }
+
+    #[test]
+    fn from_config() {
+        let config = TiledCameraConfig {
+            tile_count: UVec2::new(20, 15),
+            pixels_per_tile: UVec2::new(16, 8),
+            world_space: WorldSpace::Pixels,
+            clear_color: Some(Color::rgb(0.1, 0.2, 0.3)),
+        };
+        let bundle = TiledCameraBundle::from_config(&config);
+        let cam = &bundle.tiled_camera;
+        assert_eq!([20, 15], cam.tile_count.to_array());
+        assert_eq!([16, 8], cam.pixels_per_tile.to_array());
+        assert_eq!(WorldSpace::Pixels, cam.world_space());
+        assert_eq!([320, 120], cam.target_resolution().to_array());
+        assert!(matches!(
+            bundle.cam2d_bundle.camera.clear_color,
+            ClearColorConfig::Custom(c) if c == Color::rgb(0.1, 0.2, 0.3)
+        ));
+
+        let bundle = TiledCameraBundle::from_config(&TiledCameraConfig::default());
+        assert!(matches!(
+            bundle.cam2d_bundle.camera.clear_color,
+            ClearColorConfig::Default
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn config_round_trip() {
+        let config = TiledCameraConfig {
+            tile_count: UVec2::new(20, 15),
+            pixels_per_tile: UVec2::new(16, 8),
+            world_space: WorldSpace::Pixels,
+            clear_color: Some(Color::rgb(0.1, 0.2, 0.3)),
+        };
+        let ron = ron::to_string(&config).unwrap();
+        let loaded: TiledCameraConfig = ron::from_str(&ron).unwrap();
+        assert_eq!(config, loaded);
+
+        let loaded: TiledCameraConfig = ron::from_str(
+            "(tile_count: (10, 10), pixels_per_tile: (8, 8), world_space: Units, clear_color: None)",
+        )
+        .unwrap();
+        let bundle = TiledCameraBundle::from_config(&loaded);
+        assert_eq!([80, 80], bundle.tiled_camera.target_resolution().to_array());
+        assert_eq!(WorldSpace::Units, bundle.tiled_camera.world_space());
+    }
+
+    #[test]
+    fn snap_to_tile() {
+        let (t, cam) = unit_cam([5.0, 5.0], [10, 10]);
+        for jitter in [Vec2::ZERO, Vec2::new(0.05, -0.05), Vec2::new(-0.07, 0.09)] {
+            let p = Vec2::new(7.3, 2.6) + jitter;
+            assert_eq!([7.5, 2.5], cam.snap_to_tile_center(&t, p).to_array());
+            assert_eq!([7.0, 3.0], cam.snap_to_tile_corner(&t, p).to_array());
         }
+
+        // Odd tile counts center tiles on the camera
+        let (t, cam) = unit_cam([0.0, 0.0], [9, 9]);
+        let p = Vec2::new(1.2, -0.7);
+        assert_eq!([1.0, -1.0], cam.snap_to_tile_center(&t, p).to_array());
+        assert_eq!([1.5, -0.5], cam.snap_to_tile_corner(&t, p).to_array());
+
+        let t = GlobalTransform::IDENTITY;
+        let cam = TiledCamera::pixel_cam([10, 10], [4, 8]);
+        let p = Vec2::new(5.1, 9.0);
+        assert_eq!([6.0, 12.0], cam.snap_to_tile_center(&t, p).to_array());
+        assert_eq!([4.0, 8.0], cam.snap_to_tile_corner(&t, p).to_array());
+
+        // Outside the grid
+        let p = Vec2::new(-41.0, 99.0);
+        assert_eq!([-42.0, 100.0], cam.snap_to_tile_center(&t, p).to_array());
+        assert_eq!([-40.0, 96.0], cam.snap_to_tile_corner(&t, p).to_array());
     }
 
-    /// Retrieve the camera's [`WorldGrid`].
-    pub fn world_grid(&self) -> &WorldGrid {
-        &self.grid
+    #[test]
+    fn viewport_margins() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.viewport_margins = ViewportMargins::new(10, 30, 0, 40);
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert_eq!(1, cam.zoom());
+        assert_eq!([80, 80], cam.viewport_size().to_array());
+        assert_eq!([50, 20], cam.viewport_pos().to_array());
+
+        cam.viewport_margins = ViewportMargins::new(0, 0, 0, 80);
+        update_viewport(&mut cam, [200, 240].into(), &mut proj, &mut camera);
+        assert_eq!(2, cam.zoom());
+        assert_eq!([160, 160], cam.viewport_size().to_array());
+        assert_eq!([20, 0], cam.viewport_pos().to_array());
+
+        // Margins larger than the window
+        cam.viewport_margins = ViewportMargins::new(500, 0, 0, 500);
+        let layout = viewport_layout(&cam, [200, 160].into());
+        assert_eq!([1.0, 1.0], layout.region_size.to_array());
+        assert_eq!([199.0, 0.0], layout.pos.to_array());
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert_eq!([1, 1], cam.viewport_size().to_array());
+        assert_eq!([199, 0], cam.viewport_pos().to_array());
+
+        cam.viewport_margins = ViewportMargins::new(0, 0, 500, 0);
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert_eq!([80, 1], cam.viewport_size().to_array());
+        assert_eq!([60, 159], cam.viewport_pos().to_array());
+        let vp = camera.viewport.unwrap();
+        assert!((vp.physical_position + vp.physical_size)
+            .cmple(UVec2::new(200, 160))
+            .all());
     }
-}
 
-impl Default for TiledCamera {
-    fn default() -> Self {
-        let pixels_per_tile = UVec2::new(8, 8);
-        let tile_count = UVec2::new(80, 35);
-        Self {
-            pixels_per_tile,
-            tile_count,
-            grid: WorldGrid::unit_grid(tile_count, pixels_per_tile),
-            zoom: 1,
-            vp_size: UVec2::ONE,
-            vp_pos: UVec2::ZERO,
-            win_size: UVec2::ONE,
-            ortho_size: 0.0,
+    #[test]
+    fn world_to_screen_clamped() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+
+        let clamp = |world: Vec2| {
+            let screen = cam.world_to_screen_proj(world, proj, &t).unwrap();
+            cam.clamp_to_viewport(screen)
+        };
+        assert_eq!((Vec2::new(108.0, 88.0), false), clamp(Vec2::new(5.5, 5.5)));
+        assert_eq!((Vec2::new(20.0, 0.0), false), clamp(Vec2::new(0.0, 0.0)));
+        assert_eq!((Vec2::new(180.0, 88.0), true), clamp(Vec2::new(50.0, 5.5)));
+        assert_eq!((Vec2::new(20.0, 0.0), true), clamp(Vec2::new(-10.0, -20.0)));
+        assert_eq!((Vec2::new(100.0, 160.0), true), clamp(Vec2::new(5.0, 30.0)));
+    }
+
+    #[test]
+    fn split_screen_halves() {
+        let mut app = app_with_window([400.0, 200.0]);
+        let mut left = TiledCamera::unit_cam([10, 10], [8, 8]);
+        left.window_region = Rect::new(0.0, 0.0, 0.5, 1.0);
+        let mut right = TiledCamera::unit_cam([10, 10], [8, 8]);
+        right.window_region = Rect::new(0.5, 0.0, 1.0, 1.0);
+        let left = spawn_cam(&mut app, left);
+        let right = spawn_cam(&mut app, right);
+        app.update();
+
+        let viewport = |e: Entity| {
+            let vp = app
+                .world
+                .get::<Camera>(e)
+                .unwrap()
+                .viewport
+                .clone()
+                .unwrap();
+            (vp.physical_position, vp.physical_size)
+        };
+        let (left_pos, left_size) = viewport(left);
+        let (right_pos, right_size) = viewport(right);
+        assert_eq!([160, 160], left_size.to_array());
+        assert_eq!([160, 160], right_size.to_array());
+        assert_eq!([20, 20], left_pos.to_array());
+        assert_eq!([220, 20], right_pos.to_array());
+        assert!(left_pos.x + left_size.x <= 200);
+        assert!(right_pos.x >= 200);
+
+        let left_cam = app.world.get::<TiledCamera>(left).unwrap();
+        let right_cam = app.world.get::<TiledCamera>(right).unwrap();
+        assert_eq!(2, left_cam.zoom());
+        assert_eq!(2, right_cam.zoom());
+        assert!(!left_cam.screen_in_viewport(Vec2::new(300.0, 100.0)));
+        assert!(!right_cam.screen_in_viewport(Vec2::new(100.0, 100.0)));
+    }
+
+    #[test]
+    fn sprite_scale_for_texture() {
+        let unit = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let pixel = TiledCamera::pixel_cam([10, 10], [8, 8]);
+        assert_eq!(8.0, unit.pixels_per_unit());
+        assert_eq!(1.0, pixel.pixels_per_unit());
+
+        let (tex, tiles) = (UVec2::new(16, 16), UVec2::new(2, 2));
+        assert_eq!(
+            [0.125, 0.125],
+            unit.sprite_scale_for_texture(tex, tiles).to_array()
+        );
+        assert_eq!(
+            [1.0, 1.0],
+            pixel.sprite_scale_for_texture(tex, tiles).to_array()
+        );
+
+        let (tex, tiles) = (UVec2::new(8, 8), UVec2::new(3, 1));
+        assert_eq!(
+            [0.375, 0.125],
+            unit.sprite_scale_for_texture(tex, tiles).to_array()
+        );
+        assert_eq!(
+            [3.0, 1.0],
+            pixel.sprite_scale_for_texture(tex, tiles).to_array()
+        );
+
+        let unit = TiledCamera::unit_cam([10, 10], [4, 8]);
+        let pixel = TiledCamera::pixel_cam([10, 10], [4, 8]);
+        let (tex, tiles) = (UVec2::new(8, 8), UVec2::ONE);
+        assert_eq!(
+            [0.0625, 0.125],
+            unit.sprite_scale_for_texture(tex, tiles).to_array()
+        );
+        assert_eq!(
+            [0.5, 1.0],
+            pixel.sprite_scale_for_texture(tex, tiles).to_array()
+        );
+    }
+
+    #[test]
+    fn clamp_translation_to_bounds() {
+        let cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        assert_eq!([10.0, 10.0], cam.visible_world_size().to_array());
+
+        // View smaller than the bounds
+        let bounds = Rect::new(0.0, 0.0, 20.0, 30.0);
+        for (pos, expected) in [
+            ([2.0, 15.0], [5.0, 15.0]),
+            ([12.0, 28.0], [12.0, 25.0]),
+            ([30.0, -4.0], [15.0, 5.0]),
+        ] {
+            let p = cam.clamp_translation_to_bounds(Vec2::from(pos), bounds);
+            assert_eq!(expected, p.to_array());
+        }
+
+        // View the same size as the bounds
+        let bounds = Rect::new(-3.0, 2.0, 7.0, 12.0);
+        for pos in [[0.0, 0.0], [2.0, 7.0], [50.0, -50.0]] {
+            let p = cam.clamp_translation_to_bounds(Vec2::from(pos), bounds);
+            assert_eq!([2.0, 7.0], p.to_array());
         }
+
+        // View larger than the bounds on one axis
+        let bounds = Rect::new(0.0, 0.0, 6.0, 30.0);
+        let p = cam.clamp_translation_to_bounds(Vec2::new(-20.0, 1.0), bounds);
+        assert_eq!([3.0, 5.0], p.to_array());
+        let bounds = Rect::new(0.0, 0.0, 30.0, 6.0);
+        let p = cam.clamp_translation_to_bounds(Vec2::new(40.0, 1.0), bounds);
+        assert_eq!([25.0, 3.0], p.to_array());
+
+        // Larger on both axes
+        let bounds = Rect::new(0.0, 0.0, 4.0, 2.0);
+        let p = cam.clamp_translation_to_bounds(Vec2::new(40.0, 1.0), bounds);
+        assert_eq!([2.0, 1.0], p.to_array());
     }
-}
 
-fn on_window_resized(
-    primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
-    mut resize_events: EventReader<WindowResized>,
-    mut q_cam: Query<(&mut OrthographicProjection, &mut Camera, &mut TiledCamera)>,
-) {
-    // We need to dynamically resize the camera's viewports whenever the window
-    // size changes. A resize_event is sent when the window is first created,
-    // allowing us to reuse this system for initial setup.
-    let (primary_window_entity, primary_window) = primary_window.single();
+    #[test]
+    fn from_camera_bundle() {
+        let mut cam2d = Camera2dBundle::default();
+        cam2d.camera.order = 3;
+        cam2d.camera.hdr = true;
+        let bundle = TiledCameraBundle::from_camera_bundle(cam2d)
+            .with_clear_color(Color::RED)
+            .with_tile_count([20, 10])
+            .with_camera_position([4.0, 2.0])
+            .with_depth_range(-10.0, 10.0);
 
-    for resize_event in resize_events.read() {
-        if resize_event.window == primary_window_entity {
-            let wres = UVec2::new(
-                primary_window.physical_width(),
-                primary_window.physical_height(),
-            );
+        let camera = &bundle.cam2d_bundle.camera;
+        assert_eq!(3, camera.order);
+        assert!(camera.hdr);
+        assert!(matches!(camera.clear_color, ClearColorConfig::Custom(c) if c == Color::RED));
+        assert_eq!(
+            Vec3::new(4.0, 2.0, 0.0),
+            bundle.cam2d_bundle.transform.translation
+        );
+        assert_eq!(-10.0, bundle.cam2d_bundle.projection.near);
+        assert_eq!([20, 10], bundle.tiled_camera.tile_count.to_array());
+    }
+
+    #[test]
+    fn with_render_layers() {
+        let mut app = App::new();
+        let default = app.world.spawn(TiledCameraBundle::new()).id();
+        let layered = app
+            .world
+            .spawn(TiledCameraBundle::new().with_render_layers(RenderLayers::layer(2)))
+            .id();
+        let layers = |e: Entity| *app.world.get::<RenderLayers>(e).unwrap();
+        assert_eq!(RenderLayers::default(), layers(default));
+        assert_eq!(RenderLayers::layer(2), layers(layered));
+    }
 
-            if let Ok((mut proj, mut cam, mut tiled_cam)) = q_cam.get_single_mut() {
-                update_viewport(&mut tiled_cam, wres, &mut proj, &mut cam);
+    #[test]
+    fn contains_world_point() {
+        let e = 0.01;
+        for (t, cam) in [
+            unit_cam([7.0, -3.0], [10, 6]),
+            make_pixel_cam([7.0, -3.0], [10, 6]),
+        ] {
+            let rect = cam.visible_world_rect(&t);
+            let c = rect.center();
+            assert!(cam.contains_world_point(&t, c));
+            for (inside, outside) in [
+                (
+                    Vec2::new(rect.min.x + e, c.y),
+                    Vec2::new(rect.min.x - e, c.y),
+                ),
+                (
+                    Vec2::new(rect.max.x - e, c.y),
+                    Vec2::new(rect.max.x + e, c.y),
+                ),
+                (
+                    Vec2::new(c.x, rect.min.y + e),
+                    Vec2::new(c.x, rect.min.y - e),
+                ),
+                (
+                    Vec2::new(c.x, rect.max.y - e),
+                    Vec2::new(c.x, rect.max.y + e),
+                ),
+            ] {
+                assert!(cam.contains_world_point(&t, inside));
+                assert!(!cam.contains_world_point(&t, outside));
             }
         }
+
+        let (t, cam) = unit_cam([7.0, -3.0], [10, 6]);
+        assert_eq!(Rect::new(2.0, -6.0, 12.0, 0.0), cam.visible_world_rect(&t));
+        let (t, cam) = make_pixel_cam([7.0, -3.0], [10, 6]);
+        assert_eq!(
+            Rect::new(-33.0, -27.0, 47.0, 21.0),
+            cam.visible_world_rect(&t)
+        );
     }
-}
 
-fn on_camera_changed(
-    primary_window: Query<&Window, With<PrimaryWindow>>,
-    mut q_cam: Query<
-        (&mut OrthographicProjection, &mut Camera, &mut TiledCamera),
-        Changed<TiledCamera>,
-    >,
-) {
-    for (mut proj, mut cam, mut tiled_cam) in q_cam.iter_mut() {
-        if let Ok(window) = primary_window.get_single() {
-            let wres = UVec2::new(window.physical_width(), window.physical_height());
-            update_viewport(&mut tiled_cam, wres, &mut proj, &mut cam);
+    #[test]
+    fn pixel_snap_step() {
+        let mut app = App::new();
+        app.add_systems(Update, update_render_transform);
+        let cams = [
+            TiledCamera::unit_cam([10, 10], [8, 8]),
+            TiledCamera::unit_cam([10, 10], [4, 16]),
+            TiledCamera::pixel_cam([10, 10], [8, 8]),
+        ];
+        let entities: Vec<_> = cams
+            .into_iter()
+            .map(|cam| {
+                app.world
+                    .spawn((
+                        cam,
+                        PixelSnap,
+                        Transform::from_xyz(3.3, -4.71, 0.0),
+                        GlobalTransform::default(),
+                    ))
+                    .id()
+            })
+            .collect();
+        app.update();
+
+        for entity in entities {
+            let cam = app.world.get::<TiledCamera>(entity).unwrap();
+            let pos = app
+                .world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .translation();
+            let step = 1.0 / cam.pixels_per_unit();
+            let steps = pos.truncate() / step;
+            assert_eq!(steps.round(), steps);
+            assert!((pos.truncate() - Vec2::new(3.3, -4.71))
+                .abs()
+                .cmple(Vec2::splat(step / 2.0))
+                .all());
+            assert_eq!([3.3, -4.71], cam.logical_position().to_array());
         }
     }
-}
 
-fn update_viewport(
-    tiled_cam: &mut TiledCamera,
-    wres: UVec2,
-    proj: &mut OrthographicProjection,
-    cam: &mut Camera,
-) {
-    let tres = tiled_cam.target_resolution().as_vec2();
-    let wres = wres.as_vec2();
-    let zoom = (wres / tres).floor().min_element().max(1.0);
+    #[test]
+    fn viewport_accessors() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let cam = spawn_cam(&mut app, TiledCamera::unit_cam([10, 10], [8, 8]));
+        app.update();
+        let tiled_cam = app.world.get::<TiledCamera>(cam).unwrap();
+        assert_eq!([160, 160], tiled_cam.viewport_size().to_array());
+        assert_eq!([20, 0], tiled_cam.viewport_pos().to_array());
+        assert_eq!(
+            Rect::new(20.0, 0.0, 180.0, 160.0),
+            tiled_cam.viewport_rect()
+        );
 
-    // The 'size' of the orthographic projection.
-    //
-    // For a `FixedVertical` projection this refers to the size of the
-    // projection in vertical units.
-    let ortho_size = match tiled_cam.world_space() {
-        WorldSpace::Units => tiled_cam.tile_count.y as f32,
-        WorldSpace::Pixels => tiled_cam.tile_count.y as f32 * tiled_cam.pixels_per_tile.y as f32,
-    };
+        // Stale until the next update
+        app.world.get_mut::<TiledCamera>(cam).unwrap().tile_count = UVec2::new(20, 10);
+        let tiled_cam = app.world.get::<TiledCamera>(cam).unwrap();
+        assert_eq!(
+            Rect::new(20.0, 0.0, 180.0, 160.0),
+            tiled_cam.viewport_rect()
+        );
+        app.update();
+        let tiled_cam = app.world.get::<TiledCamera>(cam).unwrap();
+        assert_eq!(
+            Rect::new(20.0, 40.0, 180.0, 120.0),
+            tiled_cam.viewport_rect()
+        );
+    }
 
-    proj.scaling_mode = ScalingMode::FixedVertical(ortho_size);
+    #[test]
+    fn flexible_width() {
+        let mut cam = TiledCamera::unit_cam([10, 20], [8, 8]);
+        cam.responsive_mode = ResponsiveMode::FlexibleWidth;
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+
+        update_viewport(&mut cam, [1280, 720].into(), &mut proj, &mut camera);
+        assert_eq!([35, 20], cam.tile_count.to_array());
+        assert_eq!(4, cam.zoom());
+        assert_eq!([1120, 640], cam.viewport_size().to_array());
+        assert_eq!([35.0, 20.0], cam.visible_world_size().to_array());
+
+        // Exact fit
+        update_viewport(&mut cam, [1280, 640].into(), &mut proj, &mut camera);
+        assert_eq!([40, 20], cam.tile_count.to_array());
+        assert_eq!([1280, 640], cam.viewport_size().to_array());
+
+        // Narrow window
+        update_viewport(&mut cam, [480, 640].into(), &mut proj, &mut camera);
+        assert_eq!([15, 20], cam.tile_count.to_array());
 
-    let vp_size = tres * zoom;
-    let vp_pos = if wres.cmple(tres).any() {
-        Vec2::ZERO
-    } else {
-        (wres / 2.0) - (vp_size / 2.0)
+        // Non-square tiles
+        let mut cam = TiledCamera::unit_cam([10, 20], [16, 8]);
+        cam.responsive_mode = ResponsiveMode::FlexibleWidth;
+        update_viewport(&mut cam, [1280, 720].into(), &mut proj, &mut camera);
+        assert_eq!([17, 20], cam.tile_count.to_array());
     }
-    .floor();
 
-    cam.viewport = Some(Viewport {
-        physical_position: vp_pos.as_uvec2(),
-        physical_size: vp_size.as_uvec2(),
-        ..default()
-    });
+    #[test]
+    fn split_tile_offset() {
+        for (pos, index, offset) in [
+            ([3.0, -2.0], [3, -2], [0.0, 0.0]),
+            ([3.25, -1.5], [3, -2], [0.25, 0.5]),
+            ([-0.75, 0.999], [-1, 0], [0.25, 0.999]),
+        ] {
+            let (t, cam) = unit_cam(pos, [10, 10]);
+            let (i, o) = cam.split_tile_offset(&t);
+            assert_eq!(index, i.to_array());
+            assert_eq!(offset, o.to_array());
+        }
 
-    // Camera values may have been changed manually - update grid values.
-    tiled_cam.grid.tile_count = tiled_cam.tile_count;
-    tiled_cam.grid.pixels_per_tile = tiled_cam.pixels_per_tile;
-    tiled_cam.zoom = zoom as u32;
-    tiled_cam.vp_pos = vp_pos.as_uvec2();
-    tiled_cam.vp_size = vp_size.as_uvec2();
-    tiled_cam.win_size = wres.as_uvec2();
-    tiled_cam.ortho_size = ortho_size;
-}
+        for (pos, index, offset) in [
+            ([16.0, 8.0], [2, 1], [0.0, 0.0]),
+            ([12.0, -4.0], [1, -1], [4.0, 4.0]),
+            ([-0.5, 7.5], [-1, 0], [7.5, 7.5]),
+        ] {
+            let (t, cam) = make_pixel_cam(pos, [10, 10]);
+            let (i, o) = cam.split_tile_offset(&t);
+            assert_eq!(index, i.to_array());
+            assert_eq!(offset, o.to_array());
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Non-square tiles
+        let t = GlobalTransform::from_xyz(1.25, 3.5, 0.0);
+        let cam = TiledCamera::unit_cam([10, 10], [4, 8]);
+        let (i, o) = cam.split_tile_offset(&t);
+        assert_eq!([2, 3], i.to_array());
+        assert_eq!([0.25, 0.5], o.to_array());
+    }
 
-    fn unit_cam(pos: impl Point2d, tile_count: impl Size2d) -> (GlobalTransform, TiledCamera) {
-        (
-            GlobalTransform::from_translation(pos.as_vec2().extend(0.0)),
-            TiledCamera::unit_cam(tile_count, [8, 8]),
-        )
+    #[test]
+    fn min_zoom_crop() {
+        let mut cam = TiledCameraBundle::unit_cam([10, 10])
+            .with_pixels_per_tile([8, 8])
+            .with_min_zoom(2)
+            .tiled_camera;
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+
+        // Smaller than the target resolution on both axes
+        update_viewport(&mut cam, [60, 40].into(), &mut proj, &mut camera);
+        assert_eq!(2, cam.zoom());
+        assert_eq!([60, 40], cam.viewport_size().to_array());
+        assert_eq!([0, 0], cam.viewport_pos().to_array());
+        assert_eq!([10, 10], cam.world_grid().tile_count.to_array());
+        assert_eq!([3.75, 2.5], cam.visible_world_size().to_array());
+        assert!(matches!(proj.scaling_mode, ScalingMode::FixedVertical(h) if h == 2.5));
+
+        // The view center stays at the viewport center
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let proj_mat = proj.get_projection_matrix();
+        let world = cam.screen_to_world_proj(Vec2::new(30.0, 20.0), proj_mat, &t);
+        assert_eq!(Some(Vec2::new(5.0, 5.0)), world);
+
+        // Only one axis is cropped, the other is letterboxed
+        update_viewport(&mut cam, [100, 200].into(), &mut proj, &mut camera);
+        assert_eq!([100, 160], cam.viewport_size().to_array());
+        assert_eq!([0, 20], cam.viewport_pos().to_array());
+        assert_eq!([6.25, 10.0], cam.visible_world_size().to_array());
+
+        // Large enough to fit - nothing is cropped
+        update_viewport(&mut cam, [200, 160].into(), &mut proj, &mut camera);
+        assert_eq!([160, 160], cam.viewport_size().to_array());
+        assert_eq!([10.0, 10.0], cam.visible_world_size().to_array());
     }
 
-    fn make_pixel_cam(
-        pos: impl Point2d,
-        tile_count: impl Size2d,
-    ) -> (GlobalTransform, TiledCamera) {
-        (
-            GlobalTransform::from_translation(pos.as_vec2().extend(0.0)),
-            TiledCamera::pixel_cam(tile_count, [8, 8]),
-        )
+    #[test]
+    fn viewport_inside_window() {
+        let margins = [
+            ViewportMargins::default(),
+            ViewportMargins::new(199, 5, 0, 300),
+        ];
+        let regions = [Rect::new(0.0, 0.0, 1.0, 1.0), Rect::new(0.3, 0.0, 1.0, 0.5)];
+        let windows = [
+            [1, 1],
+            [60, 40],
+            [200, 160],
+            [250, 170],
+            [170, 250],
+            [1024, 768],
+        ];
+        for policy in [MinZoomPolicy::Clip, MinZoomPolicy::ReduceTiles] {
+            for mode in [
+                ResponsiveMode::Fixed,
+                ResponsiveMode::MinTiles,
+                ResponsiveMode::FlexibleWidth,
+            ] {
+                for axis in [ScaleAxis::Auto, ScaleAxis::Vertical, ScaleAxis::Horizontal] {
+                    for (integer_scaling, min_zoom, forced) in [
+                        (true, 1, None),
+                        (false, 1, None),
+                        (true, 3, None),
+                        (true, 1, Some(5)),
+                    ] {
+                        for (m, region, wres) in margins
+                            .iter()
+                            .flat_map(|m| regions.iter().map(move |r| (m, r)))
+                            .flat_map(|(m, r)| windows.iter().map(move |w| (*m, *r, *w)))
+                        {
+                            let mut cam = TiledCamera::unit_cam([40, 22], [8, 8]);
+                            cam.min_zoom_policy = policy;
+                            cam.responsive_mode = mode;
+                            cam.scale_axis = axis;
+                            cam.integer_scaling = integer_scaling;
+                            cam.min_zoom = min_zoom;
+                            cam.set_forced_zoom(forced);
+                            cam.viewport_margins = m;
+                            cam.window_region = region;
+                            let mut proj = OrthographicProjection::default();
+                            let mut camera = Camera::default();
+                            update_viewport(&mut cam, wres.into(), &mut proj, &mut camera);
+
+                            let vp = camera.viewport.unwrap();
+                            let max = vp.physical_position + vp.physical_size;
+                            assert!(
+                                max.cmple(wres.into()).all()
+                                    && vp.physical_size.cmpge(UVec2::ONE).all(),
+                                "{:?} {:?} {:?} {} {:?} {:?} {:?}: {:?} outside {:?}",
+                                policy,
+                                mode,
+                                axis,
+                                min_zoom,
+                                forced,
+                                m,
+                                region,
+                                vp,
+                                wres
+                            );
+                        }
+                    }
+                }
+            }
+        }
     }
 
     #[test]
-    fn world_to_index() {
-        let (t, cam) = unit_cam([5.0, 5.0], [3, 3]);
-        let p = cam.world_to_index(&t, [4.5, 4.5]);
-        assert_eq!([0, 0], p.to_array());
+    fn rotation() {
+        let (t, _) = unit_cam([3.0, 2.0], [10, 10]);
+        assert!(is_axis_aligned(&t));
+        let rotated = t.mul_transform(Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2)));
+        assert!(!is_axis_aligned(&rotated));
+        let flipped = t.mul_transform(Transform::from_rotation(Quat::from_rotation_z(PI)));
+        assert!(!is_axis_aligned(&flipped));
 
-        let (t, cam) = unit_cam([5.0, 5.0], [4, 4]);
-        let p = cam.world_to_index(&t, [4.5, 4.5]);
-        assert_eq!([-1, -1], p.to_array());
+        // Screen conversions use the full transform, so they still round trip.
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let proj = projection(&mut cam, [200, 160]);
+        let screen = Vec2::new(60.0, 100.0);
+        let world = cam.screen_to_world_proj(screen, proj, &rotated).unwrap();
+        assert_eq!(
+            Some(screen),
+            cam.world_to_screen_proj(world, proj, &rotated)
+        );
+    }
 
-        let (t, cam) = make_pixel_cam([16.0, 16.0], [3, 3]);
-        let p = cam.world_to_index(&t, [12.0, 12.0]);
-        assert_eq!([0, 0], p.to_array());
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "doesn't support rotation")]
+    fn rotation_unsupported() {
+        let t = GlobalTransform::from(Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2)));
+        let cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.world_to_index(&t, [1.0, 1.0]);
+    }
 
-        let (t, cam) = make_pixel_cam([16.0, 16.0], [4, 4]);
-        let p = cam.world_to_index(&t, [12.0, 12.0]);
-        assert_eq!([-1, -1], p.to_array());
+    #[test]
+    fn target_resolution_override() {
+        let mut cam = TiledCameraBundle::unit_cam([10, 10])
+            .with_pixels_per_tile([8, 8])
+            .with_target_resolution([320, 180])
+            .tiled_camera;
+        assert_eq!([320, 180], cam.target_resolution().to_array());
+        assert_eq!(None, cam.design_resolution);
+        let mut proj = OrthographicProjection::default();
+        let mut camera = Camera::default();
+
+        update_viewport(&mut cam, [1280, 720].into(), &mut proj, &mut camera);
+        assert_eq!(4, cam.zoom());
+        assert_eq!([1280, 720], cam.viewport_size().to_array());
+        assert_eq!([0, 0], cam.viewport_pos().to_array());
+        assert_eq!([40.0, 22.5], cam.visible_world_size().to_array());
+        assert_eq!([320.0, 180.0], cam.effective_resolution().to_array());
+        assert_eq!([40, 23], cam.world_grid().tile_count.to_array());
+        assert!(matches!(proj.scaling_mode, ScalingMode::FixedVertical(h) if h == 22.5));
+
+        update_viewport(&mut cam, [1000, 600].into(), &mut proj, &mut camera);
+        assert_eq!(3, cam.zoom());
+        assert_eq!([960, 540], cam.viewport_size().to_array());
+        assert_eq!([20, 30], cam.viewport_pos().to_array());
+
+        // tile_count is ignored while the override is set
+        cam.tile_count = UVec2::new(2, 2);
+        update_viewport(&mut cam, [1280, 720].into(), &mut proj, &mut camera);
+        assert_eq!([1280, 720], cam.viewport_size().to_array());
+
+        cam.target_resolution_override = None;
+        update_viewport(&mut cam, [1280, 720].into(), &mut proj, &mut camera);
+        assert_eq!([16, 16], cam.target_resolution().to_array());
+        assert_eq!([2.0, 2.0], cam.visible_world_size().to_array());
     }
 
     #[test]
-    fn index_to_world() {
-        let (t, cam) = make_pixel_cam([5, 5], [4, 4]);
-        let p = cam.index_to_tile_pos(&t, [3, 3]);
-        assert_eq!([29.0, 29.0], p.to_array());
+    fn convert_world_point() {
+        use WorldSpace::*;
+        let cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        let p = Vec2::new(2.0, -3.5);
+        assert_eq!(
+            [16.0, -28.0],
+            cam.convert_world_point(p, Units, Pixels).to_array()
+        );
+        let back = cam.convert_world_point(Vec2::new(16.0, -28.0), Pixels, Units);
+        assert_eq!(p, back);
+        assert_eq!(p, cam.convert_world_point(p, Units, Units));
+        assert_eq!(p, cam.convert_world_point(p, Pixels, Pixels));
 
-        let (t, cam) = unit_cam([5, 5], [4, 4]);
-        let p = cam.index_to_tile_pos(&t, [3, 3]);
-        assert_eq!([8.0, 8.0], p.to_array());
+        // One tile is the same size in either space
+        let cam = TiledCamera::unit_cam([10, 10], [4, 8]);
+        let cell = cam.cell_size_world();
+        let pixel_cell = TiledCamera::pixel_cam([10, 10], [4, 8]).cell_size_world();
+        assert_eq!(pixel_cell, cam.convert_world_point(cell, Units, Pixels));
 
-        let (t, cam) = make_pixel_cam([16, 16], [3, 3]);
-        let p = cam.index_to_tile_pos(&t, [3, 3]);
-        assert_eq!([36.0, 36.0], p.to_array());
+        let mut transforms = [
+            Transform::from_xyz(1.0, 2.0, 5.0),
+            Transform::from_xyz(-0.5, 0.25, -1.0),
+        ];
+        cam.convert_translations(transforms.iter_mut(), Units, Pixels);
+        assert_eq!(Vec3::new(8.0, 16.0, 5.0), transforms[0].translation);
+        assert_eq!(Vec3::new(-4.0, 2.0, -1.0), transforms[1].translation);
+        cam.convert_translations(&mut transforms, Pixels, Units);
+        assert_eq!(Vec3::new(1.0, 2.0, 5.0), transforms[0].translation);
+    }
+
+    #[test]
+    fn hidpi_logical_zoom() {
+        let cam = TiledCamera {
+            hidpi_mode: HiDpiMode::Logical,
+            ..TiledCamera::unit_cam([20, 10], [16, 18])
+        };
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Assets<Image>>()
+            .add_event::<WindowResized>()
+            .add_plugins(TiledCameraPlugin);
+        // 480x270 logical, 960x540 physical.
+        let window = Window {
+            resolution: WindowResolution::new(480.0, 270.0).with_scale_factor_override(2.0),
+            ..default()
+        };
+        app.world.spawn((window, PrimaryWindow));
+        let physical = spawn_cam(&mut app, TiledCamera::unit_cam([20, 10], [16, 18]));
+        let logical = spawn_cam(&mut app, cam);
+        app.update();
+
+        let physical = app.world.get::<TiledCamera>(physical).unwrap();
+        assert_eq!(3, physical.zoom());
+        assert_eq!(UVec2::new(960, 540), physical.viewport_size());
+
+        // One game pixel per logical pixel, IE: two physical pixels.
+        let logical = app.world.get::<TiledCamera>(logical).unwrap();
+        assert_eq!(2, logical.zoom());
+        assert_eq!(UVec2::new(640, 360), logical.viewport_size());
+        assert_eq!(UVec2::new(160, 90), logical.viewport_pos());
+    }
+
+    #[test]
+    fn index_to_screen() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([9, 9], [8, 8]);
+        // The top half of a 200x320 window, with a 2x scale factor.
+        cam.window_region = Rect::new(0.0, 0.0, 1.0, 0.5);
+        cam.scale_factor = 2.0;
+        let proj = projection(&mut cam, [200, 320]);
+        assert_eq!(UVec2::new(144, 144), cam.viewport_size());
+        assert_eq!(UVec2::new(28, 8), cam.viewport_pos());
+
+        // The center tile's center is the center of the viewport, counted
+        // from the bottom left of the window.
+        let screen = cam.index_to_screen_proj([0, 0], proj, &t).unwrap();
+        assert!(screen.distance(Vec2::new(100.0, 240.0)) < 1.0);
+        let ui = cam.screen_to_ui_position(screen);
+        assert!(ui.distance(cam.viewport_rect().center() / 2.0) < 1.0);
+
+        // Each tile is 16 pixels on screen, UI positions count down from the
+        // top in logical pixels.
+        let screen = cam.index_to_screen_proj([2, 1], proj, &t).unwrap();
+        assert!(screen.distance(Vec2::new(132.0, 256.0)) < 1.0);
+        let ui = cam.screen_to_ui_position(screen);
+        assert!(ui.distance(Vec2::new(66.0, 32.0)) < 1.0);
+
+        assert_eq!(None, cam.index_to_screen_proj([5, 0], proj, &t));
+        assert_eq!(None, cam.index_to_screen_proj([0, -5], proj, &t));
+    }
+
+    #[test]
+    fn visible_tile_count() {
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        cam.responsive_mode = ResponsiveMode::MinTiles;
+        cam.scale_axis = ScaleAxis::Vertical;
+        update_viewport(
+            &mut cam,
+            UVec2::new(404, 160),
+            &mut OrthographicProjection::default(),
+            &mut Camera::default(),
+        );
+        assert_eq!(2, cam.zoom());
+        assert_eq!(UVec2::new(25, 10), cam.visible_tile_count());
+        assert_eq!(UVec2::new(10, 10), cam.tile_count);
+
+        let mut cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        update_viewport(
+            &mut cam,
+            UVec2::new(404, 160),
+            &mut OrthographicProjection::default(),
+            &mut Camera::default(),
+        );
+        assert_eq!(UVec2::new(10, 10), cam.visible_tile_count());
+    }
+
+    #[test]
+    fn origin_centered() {
+        let t = GlobalTransform::default();
+        let cam = TiledCamera::unit_cam([4, 4], [8, 8]);
+        assert_eq!(IVec2::new(-2, -2), cam.world_to_index(&t, [-1.5, -1.5]));
+        assert_eq!(IVec2::new(0, 0), cam.world_to_index(&t, [0.5, 0.5]));
+        assert_eq!(Vec2::new(0.0, 0.0), cam.index_to_tile_pos(&t, [0, 0]));
+        assert_eq!(None, cam.world_to_index_checked(&t, [2.5, 0.0]));
+
+        let cam = TiledCamera::unit_cam([3, 3], [8, 8]);
+        assert_eq!(IVec2::new(0, 0), cam.world_to_index(&t, [0.0, 0.0]));
+        assert_eq!(Vec2::new(-0.5, -0.5), cam.index_to_tile_pos(&t, [0, 0]));
+        assert_eq!(Vec2::ZERO, cam.index_to_tile_center(&t, [0, 0]));
+    }
+
+    #[test]
+    fn origin_bottom_left() {
+        let t = GlobalTransform::default();
+        let mut cam = TiledCamera::unit_cam([4, 4], [8, 8]);
+        cam.origin = CameraOrigin::BottomLeft;
+        assert_eq!(IVec2::new(0, 0), cam.world_to_index(&t, [-1.5, -1.5]));
+        assert_eq!(IVec2::new(2, 2), cam.world_to_index(&t, [0.5, 0.5]));
+        assert_eq!(Vec2::new(-2.0, -2.0), cam.index_to_tile_pos(&t, [0, 0]));
+        assert_eq!(Vec2::new(1.0, 1.0), cam.index_to_tile_pos(&t, [3, 3]));
+        assert_eq!(
+            Some(IVec2::new(3, 3)),
+            cam.world_to_index_checked(&t, [1.5, 1.5])
+        );
+        assert_eq!(None, cam.world_to_index_checked(&t, [-2.5, 0.0]));
+
+        let mut cam = TiledCamera::unit_cam([3, 3], [8, 8]);
+        cam.origin = CameraOrigin::BottomLeft;
+        assert_eq!(IVec2::new(1, 1), cam.world_to_index(&t, [0.0, 0.0]));
+        assert_eq!(Vec2::new(-1.5, -1.5), cam.index_to_tile_pos(&t, [0, 0]));
+        assert_eq!(Vec2::ZERO, cam.index_to_tile_center(&t, [1, 1]));
+    }
+
+    #[test]
+    fn screen_space_top_bottom_split() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut top = TiledCamera::unit_cam([10, 5], [8, 8]);
+        top.window_region = Rect::new(0.0, 0.0, 1.0, 0.5);
+        let top_proj = projection(&mut top, [160, 160]);
+        let mut bottom = TiledCamera::unit_cam([10, 5], [8, 8]);
+        bottom.window_region = Rect::new(0.0, 0.5, 1.0, 1.0);
+        let bottom_proj = projection(&mut bottom, [160, 160]);
+
+        // Viewport positions count from the top left of the window.
+        assert_eq!([0, 0], top.viewport_pos().to_array());
+        assert_eq!([0, 80], bottom.viewport_pos().to_array());
+        assert_eq!([160, 80], top.viewport_size().to_array());
+
+        // Screen positions count from the bottom left.
+        let center = Vec2::new(80.0, 120.0);
+        assert!(top.screen_in_viewport(center));
+        assert!(!bottom.screen_in_viewport(center));
+        let world = top.screen_to_world_proj(center, top_proj, &t);
+        assert_eq!(Some(Vec2::new(5.0, 5.0)), world);
+        assert_eq!(None, bottom.screen_to_world_proj(center, bottom_proj, &t));
+        let screen = top.world_to_screen_proj(Vec2::new(5.0, 5.0), top_proj, &t);
+        assert_eq!(Some(center), screen);
+
+        let center = Vec2::new(80.0, 40.0);
+        assert!(bottom.screen_in_viewport(center));
+        let world = bottom.screen_to_world_proj(center, bottom_proj, &t);
+        assert_eq!(Some(Vec2::new(5.0, 5.0)), world);
+        let screen = bottom.world_to_screen_proj(Vec2::new(5.0, 5.0), bottom_proj, &t);
+        assert_eq!(Some(center), screen);
+
+        // Clamped to the top half of the window.
+        let screen = top.world_to_screen_proj(Vec2::new(5.0, -20.0), top_proj, &t);
+        assert_eq!(
+            (Vec2::new(80.0, 80.0), true),
+            top.clamp_to_viewport(screen.unwrap())
+        );
+    }
+
+    #[test]
+    fn cursor_top_half_split() {
+        let t = GlobalTransform::from_xyz(5.0, 5.0, 0.0);
+        let mut cam = TiledCamera::unit_cam([10, 5], [8, 8]);
+        cam.window_region = Rect::new(0.0, 0.0, 1.0, 0.5);
+        let proj = projection(&mut cam, [160, 160]);
+        // 80x80 logical, 160x160 physical.
+        let window = Window {
+            resolution: WindowResolution::new(80.0, 80.0).with_scale_factor_override(2.0),
+            ..default()
+        };
+
+        // The center of the top half of the window.
+        let screen_pos = cursor_to_screen(Vec2::new(40.0, 20.0), &window);
+        assert_eq!([80.0, 120.0], screen_pos.to_array());
+        let world = cam.screen_to_world_proj(screen_pos, proj, &t);
+        assert_eq!(Some(Vec2::new(5.0, 5.0)), world);
+        let pick = cam.pick_proj(screen_pos, proj, &t).unwrap();
+        assert_eq!(IVec2::ZERO, pick.tile_index);
+
+        // Anywhere in the visible viewport converts.
+        for cursor in [[1.0, 1.0], [79.0, 1.0], [1.0, 39.0], [79.0, 39.0]] {
+            let screen_pos = cursor_to_screen(cursor.into(), &window);
+            assert!(cam.screen_to_world_proj(screen_pos, proj, &t).is_some());
+        }
+
+        // The bottom half belongs to another camera.
+        let screen_pos = cursor_to_screen(Vec2::new(40.0, 60.0), &window);
+        assert_eq!(None, cam.screen_to_world_proj(screen_pos, proj, &t));
+    }
+
+    #[test]
+    fn design_resolution_builder_order() {
+        let cam = TiledCameraBundle::unit_cam([10, 10])
+            .with_design_resolution([320, 180])
+            .with_tile_count([20, 10])
+            .with_pixels_per_tile([16, 16])
+            .with_target_resolution([300, 200])
+            .tiled_camera;
+        assert_eq!(Some(UVec2::new(320, 180)), cam.design_resolution);
+
+        // Unset, the target resolution is used however it was configured.
+        let mut cam = TiledCameraBundle::unit_cam([10, 10])
+            .with_pixels_per_tile([8, 8])
+            .with_tile_count([20, 10])
+            .tiled_camera;
+        assert_eq!(None, cam.design_resolution);
+        assert_eq!(Vec2::ONE, cam.design_scale());
+        update_viewport(
+            &mut cam,
+            [320, 160].into(),
+            &mut OrthographicProjection::default(),
+            &mut Camera::default(),
+        );
+        assert_eq!(Some(UVec2::new(160, 80)), cam.design_resolution);
+    }
+
+    #[test]
+    fn letterbox_split_screen_orders() {
+        let mut app = app_with_window([200.0, 160.0]);
+        let color = Color::rgb(1.0, 0.0, 0.0);
+        for (order, region) in [
+            (0, Rect::new(0.0, 0.0, 0.5, 1.0)),
+            (1, Rect::new(0.5, 0.0, 1.0, 1.0)),
+        ] {
+            let bundle = TiledCameraBundle::unit_cam([10, 10])
+                .with_letterbox_color(color)
+                .with_window_region(region);
+            let cam = app.world.spawn(bundle).id();
+            app.world.get_mut::<Camera>(cam).unwrap().order = order;
+        }
+        app.update();
+
+        let mut orders: Vec<_> = app
+            .world
+            .query::<&Camera>()
+            .iter(&app.world)
+            .map(|c| c.order)
+            .collect();
+        orders.sort();
+        assert_eq!(vec![0, 1, LETTERBOX_ORDER, LETTERBOX_ORDER + 1], orders);
+    }
+
+    #[test]
+    fn minimized_window() {
+        let mut app = app_with_window([0.0, 0.0]);
+        let cam = spawn_cam(&mut app, TiledCamera::unit_cam([10, 10], [8, 8]));
+        app.update();
+        assert!(!app.world.get::<TiledCamera>(cam).unwrap().is_initialized());
+        assert!(app.world.get::<Camera>(cam).unwrap().viewport.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn debug_grid() {
+        use bevy::gizmos::{config::DefaultGizmoConfigGroup, AppGizmoBuilder};
+
+        let mut app = app_with_window([160.0, 120.0]);
+        // Only gizmo storage is needed to draw, the meshes built from it in
+        // `Last` require a renderer.
+        app.add_plugins(TiledCameraDebugPlugin)
+            .init_gizmo_group::<DefaultGizmoConfigGroup>();
+        let cells = app
+            .world
+            .spawn((TiledCameraBundle::unit_cam([10, 6]), DebugGrid::default()))
+            .id();
+        app.world
+            .spawn((TiledCameraBundle::pixel_cam([10, 6]), DebugGrid::bounds()));
+        let mut window = app
+            .world
+            .query_filtered::<&mut Window, With<PrimaryWindow>>()
+            .single_mut(&mut app.world);
+        window.set_cursor_position(Some(Vec2::new(80.0, 60.0)));
+        app.world.run_schedule(PostUpdate);
+
+        let mut q_cam = app
+            .world
+            .query::<(&TiledCamera, &GlobalTransform, &DebugGrid)>();
+        for (cam, transform, grid) in q_cam.iter(&app.world) {
+            assert!(cam.is_initialized());
+            let lines = grid.lines(cam, transform);
+            match grid.mode {
+                DebugGridMode::Cells => {
+                    let lines_world: Vec<_> = cam.grid_lines_world(transform).collect();
+                    assert_eq!(lines_world, lines);
+                    assert_eq!(11 + 7, lines.len());
+                }
+                DebugGridMode::Bounds => {
+                    // Every corner of the visible area is on the outline.
+                    let rect = cam.visible_world_rect(transform);
+                    assert_eq!(4, lines.len());
+                    assert_eq!(rect.min, lines[0].0);
+                    assert_eq!(rect.max, lines[2].0);
+                }
+            }
+        }
+        let cam = app.world.get::<TiledCamera>(cells).unwrap();
+        assert_eq!(UVec2::new(10, 6), cam.world_grid().tile_count);
+    }
+
+    #[test]
+    fn render_scale() {
+        let mut cam = TiledCamera::unit_cam([20, 10], [8, 8]);
+        projection(&mut cam, [320, 160]);
+        assert_eq!([320, 160], cam.render_image_size().to_array());
+
+        cam.render_scale = 0.5;
+        projection(&mut cam, [320, 160]);
+        assert_eq!([160, 80], cam.render_image_size().to_array());
+        assert_eq!([20, 10], cam.tile_count.to_array());
+        assert_eq!([20, 10], cam.world_grid().tile_count.to_array());
+        assert_eq!([320, 160], cam.viewport_size().to_array());
+
+        cam.render_scale = 4.0;
+        assert_eq!([320, 160], cam.render_image_size().to_array());
+        cam.render_scale = 0.0;
+        assert_eq!([1, 1], cam.render_image_size().to_array());
+    }
+
+    #[test]
+    fn render_scale_image() {
+        let mut app = app_with_window([320.0, 160.0]);
+        let bundle = TiledCameraBundle::unit_cam([20, 10]).with_clear_color(Color::RED);
+        let entity = app.world.spawn(bundle).id();
+        app.world.get_mut::<Camera>(entity).unwrap().order = 2;
+        app.world
+            .get_mut::<TiledCamera>(entity)
+            .unwrap()
+            .render_scale = 0.25;
+        app.update();
+
+        // The camera renders the whole image, at a quarter of the viewport size.
+        let camera = app.world.get::<Camera>(entity).unwrap();
+        assert_eq!(RENDER_SCALE_ORDER + 2, camera.order);
+        assert!(camera.viewport.is_none());
+        let RenderTarget::Image(image) = &camera.target else {
+            panic!("camera should render to an image");
+        };
+        let images = app.world.resource::<Assets<Image>>();
+        let size = images.get(image).unwrap().size();
+        assert_eq!([80, 40], size.to_array());
+        let cam = app.world.get::<TiledCamera>(entity).unwrap();
+        assert_eq!([20, 10], cam.world_grid().tile_count.to_array());
+
+        // The upscale camera takes over the camera's place in the window.
+        let mut q_upscale = app
+            .world
+            .query_filtered::<&Camera, (With<RenderScale>, Without<TiledCamera>)>();
+        let upscale = q_upscale.single(&app.world);
+        assert_eq!(2, upscale.order);
+        assert!(matches!(upscale.target, RenderTarget::Window(_)));
+        assert!(matches!(upscale.clear_color, ClearColorConfig::Custom(_)));
+        let viewport = upscale.viewport.clone().unwrap();
+        assert_eq!([320, 160], viewport.physical_size.to_array());
+
+        // The image follows the viewport size.
+        let mut window = app
+            .world
+            .query_filtered::<(Entity, &mut Window), With<PrimaryWindow>>()
+            .single_mut(&mut app.world);
+        window.1.resolution.set(160.0, 80.0);
+        let window = window.0;
+        app.world.send_event(WindowResized {
+            window,
+            width: 160.0,
+            height: 80.0,
+        });
+        app.update();
+        let camera = app.world.get::<Camera>(entity).unwrap();
+        let RenderTarget::Image(image) = &camera.target else {
+            panic!("camera should render to an image");
+        };
+        let images = app.world.resource::<Assets<Image>>();
+        assert_eq!([40, 20], images.get(image).unwrap().size().to_array());
+
+        // Back to rendering straight to the window.
+        app.world
+            .get_mut::<TiledCamera>(entity)
+            .unwrap()
+            .render_scale = 1.0;
+        app.update();
+        let camera = app.world.get::<Camera>(entity).unwrap();
+        assert_eq!(2, camera.order);
+        assert!(matches!(camera.target, RenderTarget::Window(_)));
+        let viewport = camera.viewport.clone().unwrap();
+        assert_eq!([160, 80], viewport.physical_size.to_array());
+        assert_eq!(0, q_upscale.iter(&app.world).count());
+        assert_eq!(
+            0,
+            app.world.query::<&RenderScale>().iter(&app.world).count()
+        );
+        assert!(app.world.resource::<Assets<Image>>().is_empty());
+    }
+
+    #[test]
+    fn zoom_and_remainder() {
+        let cam = TiledCamera::unit_cam([10, 10], [8, 8]);
+        for wres in [[200, 160], [81, 83], [1920, 1080], [80, 80]] {
+            let wres = UVec2::from(wres);
+            let (zoom, remainder) = cam.zoom_and_remainder(wres);
+            assert_eq!(wres - cam.target_resolution() * zoom, remainder);
+        }
+        assert_eq!(
+            (2, UVec2::new(40, 0)),
+            cam.zoom_and_remainder([200, 160].into())
+        );
+        assert_eq!(
+            (13, UVec2::new(880, 40)),
+            cam.zoom_and_remainder([1920, 1080].into())
+        );
     }
 
     #[test]